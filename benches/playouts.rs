@@ -0,0 +1,80 @@
+//! Number of games played to the end per second by the greedy [ai]
+//!
+//! Every playout starts from a clone of the [Rules] of a position, like a search would do,
+//! a clone of the [Game] copies its historic and its log besides:
+//! ```text
+//! cargo bench --bench playouts
+//! ```
+
+use stars_gapa::{
+    ai,
+    event::Event,
+    game::{Game, Rules},
+    state::State,
+};
+use std::time::{Duration, Instant};
+
+/// A playout is stopped after this many turns
+const MAX_TURNS: u32 = 500;
+
+/// Turn of the mid-game positions, with decks grown by the cards bought
+const MID_GAME: u32 = 20;
+
+/// Plays the [ai] for both players until the end of the [Rules] or the turn `until`
+fn playout(rules: &mut Rules, until: u32) {
+    while !matches!(rules.state, State::WonBy(_)) && rules.turn < until {
+        if rules.play(ai::choose_event(rules)).is_err() {
+            let _ = rules.play(Event::NextTurn);
+        }
+    }
+}
+
+/// Number of calls of `f` per second on every position, during `duration`
+fn per_second<G, T>(games: &[G], duration: Duration, f: impl Fn(&G) -> T) -> f64 {
+    let start = Instant::now();
+    let mut calls = 0;
+    while start.elapsed() < duration {
+        for game in games {
+            std::hint::black_box(f(game));
+        }
+        calls += games.len();
+    }
+    calls as f64 / start.elapsed().as_secs_f64()
+}
+
+fn main() {
+    let starts: Vec<Rules> = (0..16).map(|seed| Game::with_seed(seed).rules).collect();
+    let duration = Duration::from_secs(3);
+
+    let clones = per_second(&starts, duration, Rules::clone);
+    println!("clones: {clones:.0}/s");
+
+    // What a search clones at every node, far from the start
+    let middles: Vec<Rules> = starts
+        .iter()
+        .map(|rules| {
+            let mut rules = rules.clone();
+            playout(&mut rules, MID_GAME);
+            rules
+        })
+        .collect();
+    let clones = per_second(&middles, duration, Rules::clone);
+    let legal = per_second(&middles, duration, Rules::legal_events);
+    println!("mid-game clones: {clones:.0}/s, legal events: {legal:.0}/s");
+
+    let start = Instant::now();
+    let (mut playouts, mut turns) = (0, 0);
+    while start.elapsed() < duration {
+        for rules in &starts {
+            let mut rules = rules.clone();
+            playout(&mut rules, MAX_TURNS);
+            turns += rules.turn as usize;
+            playouts += 1;
+        }
+    }
+    let per_second = playouts as f64 / start.elapsed().as_secs_f64();
+    println!(
+        "playouts: {per_second:.0}/s, {} turns per playout",
+        turns / playouts
+    );
+}
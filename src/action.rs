@@ -1,7 +1,13 @@
-use crate::{faction::Factions, selection::Location};
+use crate::{
+    card::Card,
+    deck::Deck,
+    faction::Factions,
+    selection::Location,
+    theme::{Symbols, symbols},
+};
 use std::fmt::{Display, Formatter};
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, Hash)]
 pub enum ActionLoc {
     Hand,
     DiscardOrHand,
@@ -11,12 +17,24 @@ pub enum ActionLoc {
 impl ActionLoc {
     pub fn contains(&self, loc: &Location) -> bool {
         use Location::*;
-        match (self, loc) {
-            (ActionLoc::Hand, Hand) => true,
-            (ActionLoc::DiscardOrHand, Hand) => true,
-            (ActionLoc::DiscardOrHand, Discard) => true,
-            (ActionLoc::Shop, Shop) => true,
-            _ => false,
+        matches!(
+            (self, loc),
+            (ActionLoc::Hand, Hand)
+                | (ActionLoc::DiscardOrHand, Hand)
+                | (ActionLoc::DiscardOrHand, Discard)
+                | (ActionLoc::Shop, Shop)
+        )
+    }
+}
+
+impl ActionLoc {
+    /// Where the scrapped cards come from, in a sentence
+    pub const fn phrase(&self) -> &'static str {
+        match self {
+            ActionLoc::Hand => "from your hand",
+            ActionLoc::DiscardOrHand => "from your hand or discard pile",
+            ActionLoc::Shop => "from the shop",
+            ActionLoc::CurrentCard => "",
         }
     }
 }
@@ -32,7 +50,7 @@ impl Display for ActionLoc {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Hash)]
 pub enum Action {
     Gold(u32),
     Attack(u32),
@@ -57,38 +75,135 @@ impl Action {
     pub fn or(self, action: Action) -> Self {
         Action::Or(Box::new(self), Box::new(action))
     }
+    /// Describes an [Action] with an english sentence,
+    /// for example `Scrap this card to draw 1 card.`
+    pub fn sentence(&self) -> impl Display {
+        ActionSentence(self)
+    }
+}
+
+/// `n card` or `n cards`
+fn cards(n: u32) -> String {
+    if n == 1 {
+        String::from("1 card")
+    } else {
+        format!("{n} cards")
+    }
+}
+
+/// Lowercase part of the sentence describing an [Action]
+fn write_phrase(f: &mut Formatter<'_>, action: &Action) -> std::fmt::Result {
+    use Action::*;
+    match action {
+        Gold(i) => write!(f, "gain {i} gold"),
+        Attack(i) => write!(f, "gain {i} attack"),
+        Authority(i) => write!(f, "gain {i} authority"),
+        Discard(i) => write!(f, "discard {}", cards(*i)),
+        Scrap {
+            loc: ActionLoc::CurrentCard,
+            ..
+        } => write!(f, "scrap this card"),
+        Scrap { loc, nb } => write!(f, "scrap up to {} {}", cards(*nb), loc.phrase()),
+        Draw(i) => write!(f, "draw {}", cards(*i)),
+        OpponentDiscard(i) => write!(f, "your opponent discards {}", cards(*i)),
+        Copy => write!(f, "copy the actions of another ship played this turn"),
+        Complex { cond, action } => {
+            match cond {
+                Condition::FactionPlayed(factions) => {
+                    write!(f, "if another ")?;
+                    for (i, faction) in factions.iter().enumerate() {
+                        if i != 0 {
+                            write!(f, " or ")?;
+                        }
+                        write!(f, "{}", faction.name())?;
+                    }
+                    write!(f, " card is played, ")?;
+                }
+                Condition::Action(condition) => {
+                    write_phrase(f, condition)?;
+                    write!(f, " to ")?;
+                }
+                Condition::GreaterThan(Data::NbBases, n) => {
+                    write!(f, "if you have more than {n} bases in play, ")?
+                }
+            }
+            for (i, action) in action.iter().enumerate() {
+                if i != 0 {
+                    write!(f, " and ")?;
+                }
+                write_phrase(f, action)?;
+            }
+            Ok(())
+        }
+        Or(action1, action2) => {
+            write_phrase(f, action1)?;
+            write!(f, " or ")?;
+            write_phrase(f, action2)
+        }
+    }
+}
+
+/// Capitalized sentence describing an [Action]
+struct ActionSentence<'a>(&'a Action);
+impl Display for ActionSentence<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let phrase = Phrase(self.0).to_string();
+        let mut chars = phrase.chars();
+        if let Some(first) = chars.next() {
+            write!(f, "{}{}.", first.to_uppercase(), chars.as_str())?;
+        }
+        Ok(())
+    }
+}
+
+struct Phrase<'a>(&'a Action);
+impl Display for Phrase<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write_phrase(f, self.0)
+    }
 }
 
 /* Useful possible character
 
 shield : 🛡   🪨
 */
-pub const BASE_STR: &'static str = "🛡 ";
-pub const OUTPOST_STR: &'static str = "🪨";
-pub const GOLD_STR: &'static str = "🪙";
-pub const ATTACK_STR: &'static str = "💥";
-pub const AUTHORITY_STR: &'static str = "⚕️"; // or maybe 💊🛟
-pub const SCRAP_STR: &'static str = "🗑"; // or maybe 🧨💣
-pub const DISCARD_STR: &'static str = "♻️"; // or maybe 🧨
+pub const BASE_STR: &str = "🛡 ";
+pub const OUTPOST_STR: &str = "🪨";
+pub const GOLD_STR: &str = "🪙";
+pub const ATTACK_STR: &str = "💥";
+pub const AUTHORITY_STR: &str = "⚕️"; // or maybe 💊🛟
+pub const SCRAP_STR: &str = "🗑"; // or maybe 🧨💣
+pub const DISCARD_STR: &str = "♻️"; // or maybe 🧨
+pub const DRAW_STR: &str = "🃏";
 
+/// Writes an [Action] with the symbols of the current [Theme](crate::theme::Theme)
 impl Display for Action {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         use Action::*;
         use ActionLoc::*;
+        let Symbols {
+            gold,
+            attack,
+            authority,
+            scrap,
+            discard,
+            draw,
+            ..
+        } = symbols();
         match self {
-            Gold(i) => write!(f, "{}{GOLD_STR}", i),
-            Attack(i) => write!(f, "{}{ATTACK_STR}", i),
-            Authority(i) => write!(f, "{}{AUTHORITY_STR}", i),
-            Discard(i) => write!(f, "{}{DISCARD_STR}", i),
+            Gold(i) => write!(f, "{i}{gold}"),
+            Attack(i) => write!(f, "{i}{attack}"),
+            Authority(i) => write!(f, "{i}{authority}"),
+            Discard(i) => write!(f, "{i}{discard}"),
             Scrap {
                 loc: CurrentCard,
                 nb: 1,
-            } => write!(f, "{SCRAP_STR}"),
-            Scrap { loc, nb: 1 } => write!(f, "{SCRAP_STR} {}", loc),
-            Scrap { loc, nb } => write!(f, "{} {SCRAP_STR} {}", nb, loc),
-            Draw(i) => write!(f, "{}🃏", i),
-            OpponentDiscard(0) => write!(f, "opponent {DISCARD_STR}"),
-            OpponentDiscard(i) => write!(f, "opponent {DISCARD_STR}{}", i),
+            } => write!(f, "{scrap}"),
+            Scrap { loc, nb: 1 } => write!(f, "{scrap} {loc}"),
+            Scrap { loc, nb } => write!(f, "{nb} {scrap} {loc}"),
+            Draw(i) => write!(f, "{i}{draw}"),
+            OpponentDiscard(0) => write!(f, "opponent {discard}"),
+            OpponentDiscard(i) => write!(f, "opponent {discard}{i}"),
             Complex {
                 cond: condition,
                 action: result,
@@ -108,7 +223,7 @@ impl Display for Action {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Hash)]
 pub enum Condition {
     FactionPlayed(Factions),
     Action(Box<Action>),
@@ -120,13 +235,37 @@ impl Display for Condition {
         use Condition::*;
         match self {
             Action(action) => write!(f, "{}", action),
-            GreaterThan(data, _) => write!(f, ""),
+            GreaterThan(_, _) => write!(f, ""),
             FactionPlayed(factions) => write!(f, "{}", factions),
         }
     }
 }
 
-#[derive(Debug)]
+impl Condition {
+    /// Checks if the condition of an ability of a `card` is satisfied
+    /// by the cards `played` this turn
+    ///
+    /// Remark: the scrap conditions are chosen by the player, they are never satisfied.
+    pub fn is_satisfied(&self, card: &Card, played: &Deck) -> bool {
+        use Condition::*;
+        // The card itself is not its own ally
+        let others = || played.iter().filter(|other| other.id != card.id);
+        match self {
+            FactionPlayed(factions) => others().any(|other| {
+                other
+                    .faction
+                    .iter()
+                    .any(|faction| factions.iter().any(|ally| ally == faction))
+            }),
+            GreaterThan(Data::NbBases, n) => {
+                others().filter(|other| !other.is_ship()).count() as u32 > *n
+            }
+            Action(_) => false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Hash)]
 pub enum Data {
     NbBases,
 }
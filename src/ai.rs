@@ -0,0 +1,201 @@
+use crate::{
+    card::{Card, CardId},
+    deck::Deck,
+    event::Event,
+    game::{Game, Rules},
+    player_id::PlayerId,
+    selection::Location,
+    state::State,
+};
+
+/// Chooses the next [Event] of the current player
+///
+/// A simple greedy computer opponent: it plays its whole hand,
+/// buys the most expensive card it can afford, attacks and ends its turn.
+/// It only reads the [Rules], a search calls it on the clones of the [Rules].
+pub fn choose_event(game: &Rules) -> Event {
+    use Event::*;
+    let player = &game.players[game.current_player];
+    match game.state {
+        State::Playing => {
+            if !player.hand.is_empty() {
+                return PlayAll;
+            }
+            let affordable = game
+                .shop
+                .iter()
+                .filter(|card| card.gold <= player.gold)
+                .max_by_key(|card| card.gold);
+            if let Some(card) = affordable {
+                return Buy(card.id);
+            }
+            if game.explorer.iter().any(|card| card.gold <= player.gold) {
+                BuyExplorer
+            } else if player.attack != 0 {
+                Attack
+            } else {
+                NextTurn
+            }
+        }
+        State::Copy => Choose(player.played.iter().take(1).map(|card| card.id).collect()),
+        // The scraps and discards are never chosen
+        State::Scraping { .. } | State::Discarding { .. } => Choose(vec![]),
+        State::WonBy(_) => NextTurn,
+    }
+}
+
+/// Plays the turn of the computer when it is the current player
+///
+/// Returns `true` if an [Event] has been played.
+pub fn play(game: &mut Game, computer: PlayerId) -> bool {
+    if game.current_player != computer || matches!(game.state, State::WonBy(_)) {
+        return false;
+    }
+    if game.apply_event(choose_event(game)).is_err() {
+        // Never get stuck on a rejected event
+        let _ = game.apply_event(Event::NextTurn);
+    }
+    true
+}
+
+/// A suggested [Event] with the card to highlight and the reason of the choice
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Hint {
+    pub event: Event,
+    /// The card bought, played or chosen by the event
+    pub card: Option<CardId>,
+    /// One line explaining the choice
+    pub reason: String,
+}
+
+impl Hint {
+    fn new(event: Event, card: Option<&Card>, reason: String) -> Self {
+        Self {
+            event,
+            card: card.map(|card| card.id),
+            reason,
+        }
+    }
+}
+
+/// Recommends the next [Event] of the current player among the legal ones,
+/// `None` when no event is legal once the game is won
+///
+/// Unlike [choose_event] it plays the cards one by one, attacks as soon as it wins,
+/// scraps the starter cards and discards the cheapest cards.
+pub fn hint(game: &Game) -> Option<Hint> {
+    game.legal_events()
+        .into_iter()
+        .map(|event| rank(game, event))
+        // The first of the best ranked events
+        .min_by_key(|(rank, _)| std::cmp::Reverse(*rank))
+        .map(|(_, hint)| hint)
+}
+
+/// How good a legal [Event] is, by priority then by gold, with its [Hint]
+fn rank(game: &Game, event: Event) -> ((u8, u32), Hint) {
+    use Event::*;
+    let player = &game[game.current_player];
+    let opponent = &game[game.current_player.other()];
+    let card = |id: &CardId| {
+        let position = game.position_of(*id)?;
+        Some((position.loc, *game.card_at(&position)?))
+    };
+    let chosen = match &event {
+        Buy(id) | Play(id) | Activate(id) => card(id),
+        Choose(ids) => ids.first().and_then(card),
+        _ => None,
+    };
+    let gold = chosen.map_or(0, |(_, card)| card.gold);
+    let name = chosen.map_or("", |(_, card)| card.name());
+    let (priority, reason) = match (&game.state, &event) {
+        (State::Playing, Attack) if player.attack >= opponent.authority => (
+            9,
+            format!(
+                "Attack for {}: the opponent has {} authority left, you win",
+                player.attack, opponent.authority
+            ),
+        ),
+        (State::Playing, Play(_)) => (
+            8,
+            format!("Play {name} before buying, a card in hand gives nothing"),
+        ),
+        (State::Playing, Buy(_)) => (
+            6,
+            format!("Buy {name} for {gold} gold, the best card you can afford"),
+        ),
+        (State::Playing, BuyExplorer) => (
+            5,
+            format!(
+                "Buy an Explorer for {} gold, no card of the shop is affordable",
+                game.explorer.get(0).map_or(0, |card| card.gold)
+            ),
+        ),
+        (State::Playing, Attack) => (
+            4,
+            format!(
+                "Attack the opponent for {} of its {} authority",
+                player.attack, opponent.authority
+            ),
+        ),
+        (State::Playing, NextTurn) => (
+            3,
+            String::from("End the turn, nothing is left to play, buy or attack"),
+        ),
+        (State::Scraping { .. }, Choose(ids)) => {
+            let starters = [Card::scout().def_id(), Card::viper().def_id()];
+            match chosen {
+                Some((loc, card)) if loc != Location::Shop && starters.contains(&card.def_id()) => {
+                    (
+                        8,
+                        format!("Scrap {name}, a starter card: your better cards come back sooner"),
+                    )
+                }
+                Some((Location::Shop, _)) => (
+                    7,
+                    format!("Scrap {name} from the shop, the best card the opponent could buy"),
+                ),
+                _ if ids.is_empty() => (
+                    6,
+                    String::from("Scrap nothing, every card is worth keeping"),
+                ),
+                _ => (5, format!("Scrap {name}")),
+            }
+        }
+        // The cheapest card of the hand, discarding is not optional
+        (State::Discarding { .. }, Choose(ids)) if !ids.is_empty() => {
+            (8, format!("Discard {name}, the cheapest card of your hand"))
+        }
+        (State::Discarding { .. }, Choose(_)) => {
+            (7, String::from("Discard nothing, your hand is empty"))
+        }
+        (State::Copy, Choose(_)) => match chosen {
+            Some((_, card)) if card.is_ship() => {
+                (8, format!("Copy {name}, the best ship you played"))
+            }
+            _ => (7, format!("Copy {name}, you played no ship")),
+        },
+        _ => (0, event.to_string()),
+    };
+    let value = match (&game.state, &event) {
+        // The cheapest card is the best discard
+        (State::Discarding { .. }, _) => u32::MAX - gold,
+        // The most expensive card, then the one with a faction already owned
+        (_, Buy(_)) => {
+            let owned = chosen.is_some_and(|(_, card)| {
+                [&player.hand, &player.played, &player.discard]
+                    .into_iter()
+                    .flat_map(Deck::iter)
+                    .flat_map(|owned| owned.faction.iter())
+                    .any(|faction| card.faction.iter().any(|f| f == faction))
+            });
+            2 * gold + owned as u32
+        }
+        _ => gold,
+    };
+    let card = match event {
+        BuyExplorer => game.explorer.get(0).copied(),
+        _ => chosen.map(|(_, card)| card),
+    };
+    ((priority, value), Hint::new(event, card.as_ref(), reason))
+}
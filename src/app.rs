@@ -0,0 +1,703 @@
+//! Screens of the terminal application
+//!
+//! The [App] is a stack of [Screen]s: the main menu at the bottom,
+//! a game above it, then a pause menu above the game...
+//! Only the screen at the top receives the inputs,
+//! every screen is rendered from the bottom to the top.
+
+use crate::{
+    ai,
+    bindings::Bindings,
+    card::Card,
+    deck::Deck,
+    game::Game,
+    hot_seat::HotSeat,
+    input::Input,
+    player_id::PlayerId,
+    record::Record,
+    state::State,
+    theme::{Palette, Theme},
+    tui::view::{Gesture, Mouse},
+};
+use ratatui::{
+    crossterm::event::{Event, KeyCode},
+    layout::{Constraint, Layout},
+    prelude::{Buffer, Rect},
+    style::Style,
+    text::Line,
+    widgets::{Clear, Paragraph, Widget},
+};
+use std::{
+    fmt::Display,
+    path::{Path, PathBuf},
+    str::FromStr,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+pub mod menu;
+
+use menu::Menu;
+
+/// Who plays against the player of the terminal
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mode {
+    /// The computer plays a seat
+    Computer(PlayerId),
+    /// Two players share the terminal
+    HotSeat,
+}
+
+impl Display for Mode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Mode::Computer(seat) => write!(f, "computer {seat}"),
+            Mode::HotSeat => write!(f, "hotseat"),
+        }
+    }
+}
+
+impl FromStr for Mode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().split_once(' ') {
+            Some(("computer", seat)) => Ok(Mode::Computer(seat.parse()?)),
+            None if s.trim() == "hotseat" => Ok(Mode::HotSeat),
+            _ => Err(format!("unknown mode '{s}'")),
+        }
+    }
+}
+
+/// A [Game] played on this terminal
+#[derive(Debug)]
+pub struct Match {
+    pub hot_seat: HotSeat,
+    pub mode: Mode,
+}
+
+impl Match {
+    pub fn new(game: Game, mode: Mode) -> Self {
+        let mut hot_seat = HotSeat::new(game);
+        if let Mode::Computer(_) = mode {
+            hot_seat.waiting = None;
+        }
+        Self { hot_seat, mode }
+    }
+    pub fn game(&self) -> &Game {
+        &self.hot_seat.game
+    }
+    pub fn interact(&mut self, input: Input) {
+        self.gesture(Gesture::Input(input));
+    }
+    pub fn gesture(&mut self, gesture: Gesture) {
+        match self.mode {
+            Mode::HotSeat => self.hot_seat.gesture(gesture),
+            Mode::Computer(computer) => {
+                let HotSeat { game, view, .. } = &mut self.hot_seat;
+                if game.current_player == computer {
+                    // The cursor still moves during the turn of the computer,
+                    // the hints would tell its hand
+                    match gesture {
+                        Gesture::Input(Input::Hint) => (),
+                        Gesture::Input(input) => {
+                            let _ = view.get_event(game, input);
+                        }
+                        Gesture::Mouse(mouse) => {
+                            let _ = view.get_mouse_event(game, mouse);
+                        }
+                    }
+                } else {
+                    view.gesture(game, gesture);
+                }
+            }
+        }
+    }
+    /// Lets the computer play one [Event]
+    pub fn tick(&mut self) -> bool {
+        let HotSeat { game, view, .. } = &mut self.hot_seat;
+        let played = match self.mode {
+            Mode::Computer(computer) => ai::play(game, computer),
+            Mode::HotSeat => false,
+        };
+        if played {
+            view.refresh(game);
+        }
+        played
+    }
+}
+
+impl Widget for &Match {
+    fn render(self, area: Rect, buf: &mut Buffer)
+    where
+        Self: Sized,
+    {
+        match self.mode {
+            Mode::Computer(computer) => self
+                .game()
+                .widget(&self.hot_seat.view)
+                .hide_hand(computer)
+                .render(area, buf),
+            Mode::HotSeat => self.hot_seat.render(area, buf),
+        }
+    }
+}
+
+/// A finished [Game] played again event by event
+#[derive(Debug)]
+pub struct Replay {
+    pub record: Record,
+    /// Number of events played
+    pub step: usize,
+    pub game: Game,
+}
+
+impl Replay {
+    pub fn new(record: Record) -> Self {
+        Self {
+            game: Game::with_seed(record.seed),
+            step: 0,
+            record,
+        }
+    }
+    /// Moves to a step of the replay
+    pub fn go_to(&mut self, step: usize) {
+        let step = step.min(self.record.events.len());
+        if step < self.step {
+            self.game = self.record.replay_until(step);
+        } else {
+            for event in &self.record.events[self.step..step] {
+                let _ = self.game.apply_event(event.clone());
+            }
+        }
+        self.step = step;
+    }
+}
+
+/// Settings of the network games
+#[derive(Debug, Clone)]
+pub struct Settings {
+    /// Address of the server to host or to join
+    pub addr: String,
+    /// Name of the table to sit at
+    pub table: String,
+}
+
+/// What the terminal application has to do once the [App] is left
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Exit {
+    Quit,
+    /// Start a server and play on it
+    Host {
+        addr: String,
+        table: String,
+    },
+    /// Play on a server
+    Join {
+        addr: String,
+        table: String,
+    },
+}
+
+#[derive(Debug)]
+pub enum Screen {
+    MainMenu(Menu),
+    Match(Box<Match>),
+    Pause(Menu),
+    GameOver(Menu),
+    /// The saved games with their files
+    Load(Menu, Vec<PathBuf>),
+    /// The finished games with their files
+    Replays(Menu, Vec<PathBuf>),
+    Replay(Box<Replay>),
+    /// Every card of the game
+    Catalog(Menu, Vec<Card>),
+    /// The settings with the text of the setting being edited
+    Settings(Menu, Option<String>),
+    /// The key bindings
+    Help,
+}
+
+/// Change of the stack of [Screen]s
+enum Transition {
+    Stay,
+    Push(Screen),
+    Pop,
+    /// Replaces the screen at the top
+    Replace(Screen),
+    /// Goes back to the main menu
+    MainMenu,
+    Exit(Exit),
+}
+
+const MAIN_MENU: [&str; 9] = [
+    "Play against the computer",
+    "Hot-seat game",
+    "Host a network game",
+    "Join a network game",
+    "Load a game",
+    "Replays",
+    "Card catalog",
+    "Settings",
+    "Quit",
+];
+
+fn menu(title: &str, items: &[&str]) -> Menu {
+    Menu::new(title, items.iter().map(|item| item.to_string()).collect())
+}
+
+/// Every different [Card] of the game
+pub fn catalog() -> Vec<Card> {
+    let mut cards: Vec<Card> = vec![];
+    let decks = [Deck::starter_deck_player(), Deck::starter_complete_deck()];
+    let all = decks
+        .iter()
+        .flat_map(Deck::iter)
+        .cloned()
+        .chain([Card::explorer()]);
+    for card in all {
+        if cards.iter().all(|known| known.name() != card.name()) {
+            cards.push(card);
+        }
+    }
+    cards
+}
+
+/// The terminal application
+#[derive(Debug)]
+pub struct App {
+    screens: Vec<Screen>,
+    pub bindings: Bindings,
+    pub settings: Settings,
+    /// Directory of the saved games and of the replays
+    data_dir: PathBuf,
+    exit: Option<Exit>,
+}
+
+impl App {
+    pub fn new(bindings: Bindings, data_dir: impl Into<PathBuf>) -> Self {
+        Self {
+            screens: vec![Screen::MainMenu(menu("Stars Gapa", &MAIN_MENU))],
+            bindings,
+            settings: Settings {
+                addr: String::from("127.0.0.1:7878"),
+                table: String::from("table"),
+            },
+            data_dir: data_dir.into(),
+            exit: None,
+        }
+    }
+    /// Starts a new [Game] above the current screen
+    pub fn start(&mut self, mode: Mode) {
+        self.screens
+            .push(Screen::Match(Box::new(Match::new(Game::new(), mode))));
+    }
+    /// The screen receiving the inputs
+    pub fn screen(&self) -> &Screen {
+        self.screens.last().expect("The main menu is never left")
+    }
+    /// Takes what the terminal application has to do once the [App] is left
+    pub fn take_exit(&mut self) -> Option<Exit> {
+        self.exit.take()
+    }
+    /// Shows a message on the main menu
+    pub fn message(&mut self, message: impl Into<String>) {
+        if let Some(Screen::MainMenu(menu)) = self.screens.first_mut() {
+            menu.message = Some(message.into());
+        }
+    }
+    /// Handles an [Event] of the terminal with the key bindings,
+    /// the keys are typed as text when a setting is edited
+    pub fn handle_event(&mut self, event: &Event) {
+        if let Some(Screen::Settings(menu, Some(text))) = self.screens.last_mut()
+            && let Event::Key(key_event) = event
+        {
+            match key_event.code {
+                KeyCode::Char(c) => text.push(c),
+                KeyCode::Backspace => {
+                    text.pop();
+                }
+                KeyCode::Enter => {
+                    let text = std::mem::take(text);
+                    let selected = menu.selected;
+                    match selected {
+                        0 => self.settings.addr = text,
+                        _ => self.settings.table = text,
+                    }
+                    self.apply(Transition::Replace(self.settings_screen_at(selected)));
+                }
+                KeyCode::Esc => {
+                    let selected = menu.selected;
+                    self.apply(Transition::Replace(self.settings_screen_at(selected)));
+                }
+                _ => (),
+            }
+            return;
+        }
+        // The clicks and the moves of the mouse only matter in a game
+        if let Event::Mouse(mouse_event) = event
+            && let Some(mouse) = Mouse::from(*mouse_event)
+        {
+            if let Some(Screen::Match(game)) = self.screens.last_mut() {
+                game.gesture(Gesture::Mouse(mouse));
+                self.check_game_over();
+            }
+            return;
+        }
+        self.interact(self.bindings.input(event));
+    }
+    pub fn interact(&mut self, input: Input) {
+        let depth = self.screens.len();
+        let transition = match self.screens.last_mut() {
+            Some(Screen::Help) => match input {
+                Input::Help | Input::Quit | Input::Enter => Transition::Pop,
+                _ => Transition::Stay,
+            },
+            Some(Screen::Match(game)) => match input {
+                Input::Quit => Transition::Push(Screen::Pause(menu(
+                    "Pause",
+                    &["Resume", "Save the game", "Key bindings", "Main menu"],
+                ))),
+                Input::Help => Transition::Push(Screen::Help),
+                input => {
+                    game.interact(input);
+                    Transition::Stay
+                }
+            },
+            Some(Screen::Replay(replay)) => match input {
+                Input::Left | Input::Up => {
+                    replay.go_to(replay.step.saturating_sub(1));
+                    Transition::Stay
+                }
+                Input::Right | Input::Down | Input::Space => {
+                    replay.go_to(replay.step + 1);
+                    Transition::Stay
+                }
+                Input::Quit | Input::Enter => Transition::Pop,
+                _ => Transition::Stay,
+            },
+            Some(Screen::Catalog(menu, _)) => match input {
+                Input::Quit | Input::Enter => Transition::Pop,
+                input => {
+                    menu.interact(input);
+                    Transition::Stay
+                }
+            },
+            Some(_) if input == Input::Quit && depth > 1 => Transition::Pop,
+            Some(Screen::MainMenu(_)) if input == Input::Quit => Transition::Exit(Exit::Quit),
+            _ => self.choose(input),
+        };
+        self.apply(transition);
+        self.check_game_over();
+    }
+    /// Lets the computer play, to call regularly
+    pub fn tick(&mut self) {
+        if let Some(Screen::Match(game)) = self.screens.last_mut()
+            && game.tick()
+        {
+            self.check_game_over();
+        }
+    }
+    /// Handles the choice of an item of the menu at the top
+    fn choose(&mut self, input: Input) -> Transition {
+        let Some(screen) = self.screens.last_mut() else {
+            return Transition::Stay;
+        };
+        let (Screen::MainMenu(menu)
+        | Screen::Pause(menu)
+        | Screen::GameOver(menu)
+        | Screen::Load(menu, _)
+        | Screen::Replays(menu, _)
+        | Screen::Settings(menu, _)) = screen
+        else {
+            return Transition::Stay;
+        };
+        let Some(item) = menu.interact(input) else {
+            return Transition::Stay;
+        };
+        match screen {
+            Screen::MainMenu(menu) => match item {
+                0 => {
+                    let computer = PlayerId::random(&mut rand::rng());
+                    Transition::Push(Screen::Match(Box::new(Match::new(
+                        Game::new(),
+                        Mode::Computer(computer),
+                    ))))
+                }
+                1 => Transition::Push(Screen::Match(Box::new(Match::new(
+                    Game::new(),
+                    Mode::HotSeat,
+                )))),
+                2 => Transition::Exit(Exit::Host {
+                    addr: self.settings.addr.clone(),
+                    table: self.settings.table.clone(),
+                }),
+                3 => Transition::Exit(Exit::Join {
+                    addr: self.settings.addr.clone(),
+                    table: self.settings.table.clone(),
+                }),
+                4 => match list_files(&self.data_dir.join("saves")) {
+                    Ok(files) => Transition::Push(files_screen("Load a game", files, Screen::Load)),
+                    Err(error) => {
+                        menu.message = Some(error);
+                        Transition::Stay
+                    }
+                },
+                5 => match list_files(&self.data_dir.join("replays")) {
+                    Ok(files) => Transition::Push(files_screen("Replays", files, Screen::Replays)),
+                    Err(error) => {
+                        menu.message = Some(error);
+                        Transition::Stay
+                    }
+                },
+                6 => {
+                    let cards = catalog();
+                    let names = cards.iter().map(|card| card.name().to_string()).collect();
+                    Transition::Push(Screen::Catalog(Menu::new("Card catalog", names), cards))
+                }
+                7 => Transition::Push(self.settings_screen()),
+                _ => Transition::Exit(Exit::Quit),
+            },
+            Screen::Pause(_) => match item {
+                0 => Transition::Pop,
+                1 => {
+                    // The paused game is just below the pause menu
+                    let index = self.screens.len() - 2;
+                    let Screen::Match(game) = &self.screens[index] else {
+                        return Transition::Stay;
+                    };
+                    let message = match save(&self.data_dir.join("saves"), game) {
+                        Ok(path) => format!("Saved in {}", path.display()),
+                        Err(error) => error,
+                    };
+                    if let Some(Screen::Pause(menu)) = self.screens.last_mut() {
+                        menu.message = Some(message);
+                    }
+                    Transition::Stay
+                }
+                2 => Transition::Push(Screen::Help),
+                _ => Transition::MainMenu,
+            },
+            Screen::GameOver(_) => match item {
+                0 => {
+                    self.screens.pop();
+                    let Some(Screen::Match(game)) = self.screens.last() else {
+                        return Transition::MainMenu;
+                    };
+                    let mode = match game.mode {
+                        Mode::Computer(computer) => Mode::Computer(computer.other()),
+                        Mode::HotSeat => Mode::HotSeat,
+                    };
+                    Transition::Replace(Screen::Match(Box::new(Match::new(Game::new(), mode))))
+                }
+                1 => Transition::MainMenu,
+                _ => Transition::Exit(Exit::Quit),
+            },
+            Screen::Load(menu, files) => match load(&files[item]) {
+                Ok((mode, record)) => {
+                    Transition::Replace(Screen::Match(Box::new(Match::new(record.replay(), mode))))
+                }
+                Err(error) => {
+                    menu.message = Some(error);
+                    Transition::Stay
+                }
+            },
+            Screen::Replays(menu, files) => match load(&files[item]) {
+                Ok((_, record)) => Transition::Push(Screen::Replay(Box::new(Replay::new(record)))),
+                Err(error) => {
+                    menu.message = Some(error);
+                    Transition::Stay
+                }
+            },
+            Screen::Settings(_, editing) => match item {
+                0 => {
+                    *editing = Some(self.settings.addr.clone());
+                    Transition::Stay
+                }
+                1 => {
+                    *editing = Some(self.settings.table.clone());
+                    Transition::Stay
+                }
+                2 => {
+                    Theme::current().next().set();
+                    Transition::Replace(self.settings_screen_at(2))
+                }
+                3 => {
+                    Palette::current().next().set();
+                    Transition::Replace(self.settings_screen_at(3))
+                }
+                4 => Transition::Push(Screen::Help),
+                _ => Transition::Pop,
+            },
+            _ => Transition::Stay,
+        }
+    }
+    fn apply(&mut self, transition: Transition) {
+        match transition {
+            Transition::Stay => (),
+            Transition::Push(screen) => self.screens.push(screen),
+            Transition::Pop => {
+                if self.screens.len() > 1 {
+                    self.screens.pop();
+                }
+            }
+            Transition::Replace(screen) => {
+                self.apply(Transition::Pop);
+                self.screens.push(screen);
+            }
+            Transition::MainMenu => self.screens.truncate(1),
+            Transition::Exit(exit) => self.exit = Some(exit),
+        }
+    }
+    /// Shows the game over screen when the game at the top is won,
+    /// the finished game is kept in the replays
+    fn check_game_over(&mut self) {
+        let Some(Screen::Match(game)) = self.screens.last() else {
+            return;
+        };
+        let State::WonBy(winner) = game.game().state else {
+            return;
+        };
+        let title = match game.mode {
+            Mode::Computer(computer) if computer == winner => String::from("The computer won"),
+            Mode::Computer(_) => String::from("You won"),
+            Mode::HotSeat => format!("The {winner} player won"),
+        };
+        let mut menu = menu(&title, &["Rematch", "Main menu", "Quit"]);
+        if let Err(error) = save(&self.data_dir.join("replays"), game) {
+            menu.message = Some(error);
+        }
+        self.screens.push(Screen::GameOver(menu));
+    }
+    fn settings_screen(&self) -> Screen {
+        self.settings_screen_at(0)
+    }
+    /// The settings with an item selected
+    fn settings_screen_at(&self, selected: usize) -> Screen {
+        let mut menu = Menu::new(
+            "Settings",
+            vec![
+                format!("Server address: {}", self.settings.addr),
+                format!("Table name: {}", self.settings.table),
+                format!("Theme: {}", Theme::current()),
+                format!("Colours: {}", Palette::current()),
+                String::from("Key bindings"),
+                String::from("Back"),
+            ],
+        );
+        menu.selected = selected;
+        Screen::Settings(menu, None)
+    }
+}
+
+fn files_screen(
+    title: &str,
+    files: Vec<PathBuf>,
+    screen: fn(Menu, Vec<PathBuf>) -> Screen,
+) -> Screen {
+    let mut menu = Menu::new(
+        title,
+        files
+            .iter()
+            .filter_map(|file| file.file_stem())
+            .map(|name| name.to_string_lossy().into_owned())
+            .collect(),
+    );
+    if files.is_empty() {
+        menu.message = Some(String::from("No game yet"));
+    }
+    screen(menu, files)
+}
+
+/// Files of a directory from the newest to the oldest,
+/// there is no file when the directory does not exist
+fn list_files(dir: &Path) -> Result<Vec<PathBuf>, String> {
+    let entries = match std::fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => return Ok(vec![]),
+        Err(error) => return Err(format!("{}: {error}", dir.display())),
+    };
+    let mut files: Vec<PathBuf> = entries
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .filter(|path| path.is_file())
+        .collect();
+    files.sort();
+    files.reverse();
+    Ok(files)
+}
+
+/// Writes the [Mode] and the [Record] of a game in a new file of a directory
+pub fn save(dir: &Path, game: &Match) -> Result<PathBuf, String> {
+    std::fs::create_dir_all(dir).map_err(|error| format!("{}: {error}", dir.display()))?;
+    let time = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |time| time.as_millis());
+    let path = dir.join(format!("{time}.game"));
+    let content = format!("mode {}\n{}", game.mode, game.game().record());
+    std::fs::write(&path, content).map_err(|error| format!("{}: {error}", path.display()))?;
+    Ok(path)
+}
+
+/// Reads a file written by [save]
+pub fn load(path: &Path) -> Result<(Mode, Record), String> {
+    let error = |error: String| format!("{}: {error}", path.display());
+    let content = std::fs::read_to_string(path).map_err(|e| error(e.to_string()))?;
+    let (mode, record) = content.split_once('\n').unwrap_or((&content, ""));
+    let mode = match mode.strip_prefix("mode ") {
+        Some(mode) => mode.parse().map_err(error)?,
+        None => return Err(error(String::from("missing the mode of the game"))),
+    };
+    Ok((mode, record.parse().map_err(error)?))
+}
+
+impl Widget for &App {
+    fn render(self, area: Rect, buf: &mut Buffer)
+    where
+        Self: Sized,
+    {
+        for screen in &self.screens {
+            match screen {
+                Screen::MainMenu(menu) => {
+                    Clear.render(area, buf);
+                    menu.render(area, buf);
+                }
+                Screen::Match(game) => game.render(area, buf),
+                Screen::Pause(menu)
+                | Screen::GameOver(menu)
+                | Screen::Load(menu, _)
+                | Screen::Replays(menu, _) => menu.render(area, buf),
+                Screen::Settings(menu, editing) => {
+                    let mut menu = menu.clone();
+                    if let Some(text) = editing {
+                        let item = &mut menu.items[menu.selected];
+                        let label = item.split_once(": ").map_or("", |(label, _)| label);
+                        *item = format!("{label}: {text}_");
+                        menu.message = Some(String::from("[enter] confirm  [esc] cancel"));
+                    }
+                    menu.render(area, buf);
+                }
+                Screen::Replay(replay) => {
+                    replay.game.render(area, buf);
+                    let line = Line::from(format!(
+                        " Replay {}/{} [<-][->] step [q] back ",
+                        replay.step,
+                        replay.record.events.len()
+                    ))
+                    .style(Style::new().reversed());
+                    Paragraph::new(line).centered().render(area, buf);
+                }
+                Screen::Catalog(menu, cards) => {
+                    Clear.render(area, buf);
+                    let [list, zoom] =
+                        Layout::horizontal([Constraint::Length(30), Constraint::Fill(1)])
+                            .areas(area);
+                    menu.render(list, buf);
+                    if let Some(card) = cards.get(menu.selected) {
+                        card.zoom_widget().render(zoom, buf);
+                    }
+                }
+                Screen::Help => self.bindings.render(area, buf),
+            }
+        }
+    }
+}
@@ -0,0 +1,95 @@
+use crate::input::Input;
+use ratatui::{
+    layout::Constraint,
+    prelude::{Buffer, Rect},
+    style::Style,
+    text::Line,
+    widgets::{Block, BorderType, Clear, Paragraph, Widget},
+};
+
+/// A vertical list of items with one of them selected
+#[derive(Debug, Clone)]
+pub struct Menu {
+    pub title: String,
+    pub items: Vec<String>,
+    pub selected: usize,
+    /// Shown under the items, for example when an action failed
+    pub message: Option<String>,
+}
+
+impl Menu {
+    pub fn new(title: impl Into<String>, items: Vec<String>) -> Self {
+        Self {
+            title: title.into(),
+            items,
+            selected: 0,
+            message: None,
+        }
+    }
+    /// Moves the selection with the arrows,
+    /// returns the index of the item chosen with enter or space
+    pub fn interact(&mut self, input: Input) -> Option<usize> {
+        use Input::*;
+        match input {
+            Up | Left => self.selected = self.selected.saturating_sub(1),
+            Down | Right => {
+                self.selected = (self.selected + 1).min(self.items.len().saturating_sub(1))
+            }
+            Enter | Space if self.selected < self.items.len() => return Some(self.selected),
+            _ => (),
+        }
+        None
+    }
+}
+
+impl Widget for &Menu {
+    fn render(self, area: Rect, buf: &mut Buffer)
+    where
+        Self: Sized,
+    {
+        let message = self.message.as_deref().unwrap_or("");
+        let width = self
+            .items
+            .iter()
+            .map(|item| item.chars().count() + 4)
+            .chain([self.title.chars().count(), message.chars().count()])
+            .max()
+            .unwrap_or(0) as u16
+            + 4;
+        let message_height = if message.is_empty() { 0 } else { 2 };
+        let height = (self.items.len() as u16 + message_height + 2).min(area.height);
+        let area = area.centered(Constraint::Length(width), Constraint::Length(height));
+
+        // Only the items around the selected one fit in a small area
+        let visible = height.saturating_sub(2 + message_height).max(1) as usize;
+        let first = self.selected.saturating_sub(visible - 1);
+        let mut lines: Vec<Line> = self
+            .items
+            .iter()
+            .enumerate()
+            .skip(first)
+            .take(visible)
+            .map(|(i, item)| {
+                if i == self.selected {
+                    Line::from(format!("> {item} <")).style(Style::new().bold().blue())
+                } else {
+                    Line::from(item.as_str())
+                }
+            })
+            .collect();
+        if !message.is_empty() {
+            lines.push(Line::from(""));
+            lines.push(Line::from(message).style(Style::new().yellow()));
+        }
+
+        Clear.render(area, buf);
+        Paragraph::new(lines)
+            .centered()
+            .block(
+                Block::bordered()
+                    .border_type(BorderType::Rounded)
+                    .title_top(Line::from(self.title.as_str()).centered()),
+            )
+            .render(area, buf);
+    }
+}
@@ -0,0 +1,208 @@
+use crate::input::{Input, KEY_INPUTS};
+use ratatui::{
+    crossterm::event::{Event, KeyCode},
+    layout::Constraint,
+    prelude::{Buffer, Rect},
+    style::Style,
+    text::Line,
+    widgets::{Block, Clear, Paragraph, Widget},
+};
+use std::{fmt::Display, path::Path, str::FromStr};
+
+/// Keys bound to each [Input]
+///
+/// The bindings are written in a file with one input per line:
+/// ```text
+/// # comment
+/// left = Left h
+/// undo = u Backspace
+/// ```
+/// The inputs missing from the file keep their default keys.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Bindings {
+    keys: Vec<(Input, Vec<KeyCode>)>,
+}
+
+impl Default for Bindings {
+    fn default() -> Self {
+        use KeyCode::*;
+        Self {
+            keys: vec![
+                (Input::Enter, vec![Enter]),
+                (Input::Space, vec![Char(' ')]),
+                (Input::Left, vec![Left, Char('h')]),
+                (Input::Right, vec![Right, Char('l')]),
+                (Input::Up, vec![Up, Char('k')]),
+                (Input::Down, vec![Down, Char('j')]),
+                (Input::Undo, vec![Char('u')]),
+                (Input::EndTurn, vec![Char('e')]),
+                (Input::PlayAll, vec![Char('a')]),
+                (Input::Cancel, vec![Backspace, Char('c')]),
+                (Input::Zoom, vec![Char('z')]),
+                (Input::Hint, vec![Char('H')]),
+                (Input::Log, vec![Char('L')]),
+                (Input::ScrollUp, vec![PageUp]),
+                (Input::ScrollDown, vec![PageDown]),
+                (Input::Help, vec![Char('?'), F(1)]),
+                (Input::Quit, vec![Esc, Char('q')]),
+            ],
+        }
+    }
+}
+
+impl Bindings {
+    /// Reads the bindings from a file,
+    /// the default bindings are used when there is no file
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, String> {
+        let path = path.as_ref();
+        match std::fs::read_to_string(path) {
+            Ok(content) => content
+                .parse()
+                .map_err(|error| format!("{}: {error}", path.display())),
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(error) => Err(format!("{}: {error}", path.display())),
+        }
+    }
+    /// [Input] bound to a terminal [Event]
+    pub fn input(&self, event: &Event) -> Input {
+        match event {
+            Event::Key(key_event) => self
+                .keys
+                .iter()
+                .find(|(_, keys)| keys.contains(&key_event.code))
+                .map_or(Input::Other, |(input, _)| *input),
+            Event::Mouse(mouse_event) => Input::from_mouse(*mouse_event),
+            _ => Input::Other,
+        }
+    }
+    /// Keys bound to an [Input]
+    pub fn keys(&self, input: Input) -> &[KeyCode] {
+        self.keys
+            .iter()
+            .find(|(bound, _)| *bound == input)
+            .map_or(&[], |(_, keys)| keys)
+    }
+    /// Binds keys to an [Input], the keys are unbound from the other inputs
+    pub fn bind(&mut self, input: Input, keys: Vec<KeyCode>) {
+        for (_, bound) in &mut self.keys {
+            bound.retain(|key| !keys.contains(key));
+        }
+        match self.keys.iter_mut().find(|(bound, _)| *bound == input) {
+            Some((_, bound)) => *bound = keys,
+            None => self.keys.push((input, keys)),
+        }
+    }
+}
+
+/// Name of a key in the bindings file
+pub fn key_name(key: &KeyCode) -> String {
+    use KeyCode::*;
+    match key {
+        Char(' ') => String::from("Space"),
+        Char(c) => c.to_string(),
+        F(n) => format!("F{n}"),
+        key => format!("{key:?}"),
+    }
+}
+
+/// Parses a key written by [key_name]
+pub fn parse_key(name: &str) -> Result<KeyCode, String> {
+    use KeyCode::*;
+    let mut chars = name.chars();
+    if let (Some(c), None) = (chars.next(), chars.next()) {
+        return Ok(Char(c));
+    }
+    let key = match name {
+        "Space" => Char(' '),
+        "Enter" => Enter,
+        "Esc" => Esc,
+        "Backspace" => Backspace,
+        "Tab" => Tab,
+        "Delete" => Delete,
+        "Insert" => Insert,
+        "Home" => Home,
+        "End" => End,
+        "PageUp" => PageUp,
+        "PageDown" => PageDown,
+        "Left" => Left,
+        "Right" => Right,
+        "Up" => Up,
+        "Down" => Down,
+        _ => match name.strip_prefix('F').map(str::parse) {
+            Some(Ok(n)) => F(n),
+            _ => return Err(format!("unknown key '{name}'")),
+        },
+    };
+    Ok(key)
+}
+
+/// Writes the bindings in the format of the bindings file
+impl Display for Bindings {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (input, keys) in &self.keys {
+            write!(f, "{} =", input.name())?;
+            for key in keys {
+                write!(f, " {}", key_name(key))?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Bindings {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut bindings = Self::default();
+        for (i, line) in s.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let error = |error: String| format!("line {}: {error}", i + 1);
+            let (input, keys) = line
+                .split_once('=')
+                .ok_or_else(|| error(String::from("expected 'input = keys'")))?;
+            let input: Input = input.trim().parse().map_err(error)?;
+            if !KEY_INPUTS.contains(&input) {
+                return Err(error(format!("'{input}' cannot be bound to keys")));
+            }
+            let keys = keys
+                .split_whitespace()
+                .map(parse_key)
+                .collect::<Result<_, _>>()
+                .map_err(error)?;
+            bindings.bind(input, keys);
+        }
+        Ok(bindings)
+    }
+}
+
+/// Screen listing the key of every [Input]
+impl Widget for &Bindings {
+    fn render(self, area: Rect, buf: &mut Buffer)
+    where
+        Self: Sized,
+    {
+        let lines: Vec<Line> = KEY_INPUTS
+            .iter()
+            .map(|input| {
+                let keys: Vec<String> = self.keys(*input).iter().map(key_name).collect();
+                Line::from(format!("{:>20}  {}", keys.join(" "), input.description()))
+            })
+            .collect();
+        let area = area.centered(
+            Constraint::Length(80),
+            Constraint::Length(lines.len() as u16 + 2),
+        );
+        Clear.render(area, buf);
+        Paragraph::new(lines)
+            .block(
+                Block::bordered()
+                    .title_top(Line::from("Key bindings").centered())
+                    .border_style(Style::new().blue()),
+            )
+            .render(area, buf);
+    }
+}
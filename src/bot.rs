@@ -0,0 +1,355 @@
+//! Bots written in any language, playing over their standard input and output
+//!
+//! A bot is a program started by the engine with `sh -c <command>`.
+//! The engine writes lines to its standard input and the bot answers with lines
+//! on its standard output, its lines starting with `#` are ignored.
+//!
+//! The engine starts with `hello <version> <seat>`, the bot answers `ready`:
+//! ```text
+//! > hello 1 2nd
+//! < ready
+//! ```
+//! When the bot has to play, the engine writes the [position] seen by its seat
+//! with the legal moves and `go`, the bot answers with one of the moves:
+//! ```text
+//! > position
+//! > turn 2
+//! > state playing
+//! > you 0 0 50
+//! > opponent 0 1 50
+//! > card #3 2 Battle Pod
+//! > ...
+//! > shop #3 #16 #22 #27 #9
+//! > explorer #0
+//! > hand #40 #47 #44 #46 #41
+//! > played
+//! > discard
+//! > draw 5
+//! > opponent-hand 0
+//! > opponent-played
+//! > opponent-discard #32 #33 #35
+//! > opponent-draw 7
+//! > move buy #3
+//! > move play #40
+//! > move end
+//! > go
+//! < play #40
+//! ```
+//! The players are written `<gold> <attack> <authority>`, the cards `card <id> <price> <name>`.
+//! The states are `playing`, `scraping <nb> <hand|discard-or-hand|shop|current>`,
+//! `discarding <nb>`, `copy` and `won <1st|2nd>`.
+//!
+//! An answer that is not a legal move is answered `illegal <reason>`
+//! and the bot answers again. A bot answering [MAX_ILLEGAL] illegal moves in a row,
+//! too slow or stopped forfeits the game. At the end of the game the engine writes
+//! `gameover <winner>` and closes the standard input of the bot, the bot is killed
+//! if it has not stopped [EXIT_TIMEOUT] later. The lines written by the bot
+//! after its answer are thrown away before the next `go`.
+
+use crate::{
+    action::ActionLoc, ai, deck::Deck, event::Event, game::Game, player_id::PlayerId, state::State,
+};
+use std::{
+    fmt::Display,
+    io::{BufRead, BufReader, Write},
+    process::{Child, ChildStdin, Command, Stdio},
+    sync::mpsc::{Receiver, RecvTimeoutError, channel},
+    time::{Duration, Instant},
+};
+
+/// Version of the protocol, sent in the `hello` line
+pub const VERSION: u32 = 1;
+/// Time given to a bot to answer by default
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(5);
+/// Number of illegal answers in a row making a bot forfeit
+pub const MAX_ILLEGAL: usize = 3;
+/// Time given to a bot to stop once its input is closed
+pub const EXIT_TIMEOUT: Duration = Duration::from_millis(500);
+
+/// Why a bot forfeits
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BotError {
+    /// The bot did not answer in time
+    Timeout,
+    /// The bot has stopped or closed its output
+    Stopped,
+    /// The last of the illegal answers of the bot
+    Illegal(String),
+    /// The bot cannot be started or written to
+    Io(String),
+}
+
+impl Display for BotError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BotError::Timeout => write!(f, "the bot did not answer in time"),
+            BotError::Stopped => write!(f, "the bot has stopped"),
+            BotError::Illegal(answer) => write!(f, "the bot played illegal moves: {answer}"),
+            BotError::Io(error) => write!(f, "the bot cannot be reached: {error}"),
+        }
+    }
+}
+
+impl From<std::io::Error> for BotError {
+    fn from(error: std::io::Error) -> Self {
+        BotError::Io(error.to_string())
+    }
+}
+
+/// A bot program playing a seat
+#[derive(Debug)]
+pub struct Bot {
+    child: Child,
+    /// Closed when the bot is dropped
+    input: Option<ChildStdin>,
+    /// Lines written by the bot, read by a thread
+    output: Receiver<String>,
+    pub timeout: Duration,
+}
+
+impl Bot {
+    /// Starts a bot with a shell `command` and waits for it to be ready
+    pub fn spawn(command: &str, seat: PlayerId, timeout: Duration) -> Result<Self, BotError> {
+        let mut child = Command::new("sh")
+            .arg("-c")
+            .arg(command)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .spawn()?;
+        let input = child.stdin.take().ok_or(BotError::Stopped)?;
+        let stdout = child.stdout.take().ok_or(BotError::Stopped)?;
+        let (sender, output) = channel();
+        std::thread::spawn(move || {
+            for line in BufReader::new(stdout).lines() {
+                let Ok(line) = line else { break };
+                if !line.starts_with('#') && sender.send(line.trim().to_string()).is_err() {
+                    break;
+                }
+            }
+        });
+        let mut bot = Self {
+            child,
+            input: Some(input),
+            output,
+            timeout,
+        };
+        bot.send(format_args!("hello {VERSION} {seat}"))?;
+        match bot.recv()?.as_str() {
+            "ready" => Ok(bot),
+            answer => Err(BotError::Io(format!("'{answer}' instead of 'ready'"))),
+        }
+    }
+    fn send(&mut self, text: impl Display) -> Result<(), BotError> {
+        let input = self.input.as_mut().ok_or(BotError::Stopped)?;
+        writeln!(input, "{text}")?;
+        input.flush()?;
+        Ok(())
+    }
+    fn recv(&self) -> Result<String, BotError> {
+        self.output
+            .recv_timeout(self.timeout)
+            .map_err(|error| match error {
+                RecvTimeoutError::Timeout => BotError::Timeout,
+                RecvTimeoutError::Disconnected => BotError::Stopped,
+            })
+    }
+    /// Asks the bot for the next legal [Event] of the current player
+    pub fn choose(&mut self, game: &Game) -> Result<Event, BotError> {
+        let legal = game.legal_events();
+        // A late answer to a previous position is not an answer to this one
+        while self.output.try_recv().is_ok() {}
+        self.send(position(game, game.current_player, &legal))?;
+        let mut illegal = 0;
+        loop {
+            let answer = self.recv()?;
+            let error = match answer.parse::<Event>() {
+                Ok(event) if legal.contains(&event) => return Ok(event),
+                Ok(event) => format!("'{event}' is not a legal move"),
+                Err(error) => error,
+            };
+            illegal += 1;
+            if illegal == MAX_ILLEGAL {
+                return Err(BotError::Illegal(answer));
+            }
+            self.send(format_args!("illegal {error}"))?;
+        }
+    }
+    /// Tells the bot who won, closes its input and waits for it to stop
+    pub fn game_over(mut self, winner: PlayerId) {
+        let _ = self.send(format_args!("gameover {winner}"));
+    }
+}
+
+/// Closes the input of the bot, the bot is killed if it has not stopped after [EXIT_TIMEOUT]
+impl Drop for Bot {
+    fn drop(&mut self) {
+        self.input = None;
+        let closed = Instant::now();
+        while closed.elapsed() < EXIT_TIMEOUT {
+            if !matches!(self.child.try_wait(), Ok(None)) {
+                return;
+            }
+            std::thread::sleep(Duration::from_millis(10));
+        }
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+/// The [Game] seen by a seat, with the legal moves, written as in the protocol
+pub fn position<'a>(game: &'a Game, seat: PlayerId, legal: &'a [Event]) -> impl Display + 'a {
+    Position { game, seat, legal }
+}
+
+struct Position<'a> {
+    game: &'a Game,
+    seat: PlayerId,
+    legal: &'a [Event],
+}
+
+/// `#3 #16 #22`
+struct Ids<'a>(&'a Deck);
+impl Display for Ids<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for card in self.0.iter() {
+            write!(f, " {}", card.id)?;
+        }
+        Ok(())
+    }
+}
+
+impl Display for Position<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let Position { game, seat, legal } = *self;
+        let (me, opponent) = (&game[seat], &game[seat.other()]);
+        writeln!(f, "position")?;
+        writeln!(f, "turn {}", game.turn)?;
+        match &game.state {
+            State::Playing => writeln!(f, "state playing")?,
+            State::Scraping { nb, loc } => {
+                let loc = match loc {
+                    ActionLoc::Hand => "hand",
+                    ActionLoc::DiscardOrHand => "discard-or-hand",
+                    ActionLoc::Shop => "shop",
+                    ActionLoc::CurrentCard => "current",
+                };
+                writeln!(f, "state scraping {nb} {loc}")?
+            }
+            State::Discarding { nb, .. } => writeln!(f, "state discarding {nb}")?,
+            State::Copy => writeln!(f, "state copy")?,
+            State::WonBy(winner) => writeln!(f, "state won {winner}")?,
+        }
+        for (who, player) in [("you", me), ("opponent", opponent)] {
+            writeln!(
+                f,
+                "{who} {} {} {}",
+                player.gold, player.attack, player.authority
+            )?;
+        }
+        let visible = [
+            &game.shop,
+            &game.explorer,
+            &me.hand,
+            &me.played,
+            &me.discard,
+            &opponent.played,
+            &opponent.discard,
+        ];
+        for card in visible.into_iter().flat_map(Deck::iter) {
+            writeln!(f, "card {} {} {}", card.id, card.gold, card.name())?;
+        }
+        writeln!(f, "shop{}", Ids(&game.shop))?;
+        writeln!(f, "explorer{}", Ids(&game.explorer))?;
+        writeln!(f, "hand{}", Ids(&me.hand))?;
+        writeln!(f, "played{}", Ids(&me.played))?;
+        writeln!(f, "discard{}", Ids(&me.discard))?;
+        writeln!(f, "draw {}", me.draw_pile.len())?;
+        writeln!(f, "opponent-hand {}", opponent.hand.len())?;
+        writeln!(f, "opponent-played{}", Ids(&opponent.played))?;
+        writeln!(f, "opponent-discard{}", Ids(&opponent.discard))?;
+        writeln!(f, "opponent-draw {}", opponent.draw_pile.len())?;
+        for event in legal {
+            writeln!(f, "move {event}")?;
+        }
+        write!(f, "go")
+    }
+}
+
+/// Who plays a seat of a [bot match](play_match)
+#[derive(Debug)]
+pub enum Seat {
+    /// The greedy computer player of [ai]
+    Computer,
+    Bot(Bot),
+}
+
+impl Seat {
+    fn choose(&mut self, game: &Game) -> Result<Event, BotError> {
+        match self {
+            Seat::Computer => Ok(ai::choose_event(game)),
+            Seat::Bot(bot) => bot.choose(game),
+        }
+    }
+}
+
+/// How a [bot match](play_match) ended
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Outcome {
+    Won(PlayerId),
+    Forfeit {
+        loser: PlayerId,
+        error: BotError,
+    },
+    /// Nobody has won after the maximum number of turns
+    Unfinished,
+}
+
+impl Display for Outcome {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Outcome::Won(winner) => write!(f, "won by the {winner} player"),
+            Outcome::Forfeit { loser, error } => {
+                write!(f, "the {loser} player forfeits: {error}")
+            }
+            Outcome::Unfinished => write!(f, "unfinished"),
+        }
+    }
+}
+
+/// Plays a [Game] between two seats until it is won or `max_turns` are played
+pub fn play_match(game: &mut Game, mut seats: [Seat; 2], max_turns: u32) -> Outcome {
+    let outcome = loop {
+        if let State::WonBy(winner) = game.state {
+            break Outcome::Won(winner);
+        }
+        if game.turn > max_turns {
+            break Outcome::Unfinished;
+        }
+        let player = game.current_player;
+        match seats[player].choose(game) {
+            Ok(event) => {
+                if game.apply_event(event).is_err() {
+                    // The computer never gets stuck on a rejected event
+                    let _ = game.apply_event(Event::NextTurn);
+                }
+            }
+            Err(error) => {
+                break Outcome::Forfeit {
+                    loser: player,
+                    error,
+                };
+            }
+        }
+    };
+    let winner = match &outcome {
+        Outcome::Won(winner) => Some(*winner),
+        Outcome::Forfeit { loser, .. } => Some(loser.other()),
+        Outcome::Unfinished => None,
+    };
+    for seat in seats {
+        if let (Seat::Bot(bot), Some(winner)) = (seat, winner) {
+            bot.game_over(winner);
+        }
+    }
+    outcome
+}
@@ -1,24 +1,60 @@
-use crate::{
-    action::{Action, BASE_STR, OUTPOST_STR},
-    faction::Factions,
+use crate::{action::Action, faction::Factions};
+use std::{
+    fmt::Display,
+    hash::{Hash, Hasher},
+    ops::Deref,
+    slice::Iter,
 };
-use ratatui::{
-    prelude::{Buffer, Rect},
-    style::Style,
-    text::Line,
-    widgets::{Block, BorderType, Paragraph, Widget},
-};
-use std::{fmt::Display, slice::Iter};
 
 pub mod collection;
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Hash)]
 pub enum Life {
     OutPost(u32),
     Base(u32),
 }
-#[derive(Debug, Default)]
-pub struct Card {
+/// Identifies a [Card] of a [Game](crate::game::Game),
+/// it does not change when the card moves from a [Deck](crate::deck::Deck) to another
+///
+/// Remark: the cards created outside of a game all have the id 0.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct CardId(pub u32);
+
+/// Written `#12`
+impl Display for CardId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "#{}", self.0)
+    }
+}
+
+/// Parses a [CardId] written `#12`
+impl std::str::FromStr for CardId {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.strip_prefix('#')
+            .and_then(|id| id.parse().ok())
+            .map(CardId)
+            .ok_or_else(|| format!("'{s}' is not a card id like #12"))
+    }
+}
+
+/// Identifies the definition of a [Card], shared by all its copies
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct DefId(pub &'static str);
+
+impl Display for DefId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// What a [Card] is: its name, its price and its actions
+///
+/// A definition is built once and shared by all the copies of the card,
+/// see [Card::viper] and the other cards of the collection.
+#[derive(Debug, Clone, Default, Hash)]
+pub struct CardDef {
     /// Name of a [Card]
     name: &'static str,
     /// All the action(s) of a [Card]
@@ -30,8 +66,8 @@ pub struct Card {
     pub life: Option<Life>,
 }
 
-impl Card {
-    pub const DEFAULT: Self = Card {
+impl CardDef {
+    pub const DEFAULT: Self = CardDef {
         name: "",
         actions: vec![],
         gold: 0,
@@ -42,20 +78,14 @@ impl Card {
         self.life.is_none()
     }
     pub const fn is_base(&self) -> bool {
-        match self.life {
-            Some(Life::Base(_)) => true,
-            _ => false,
-        }
+        matches!(self.life, Some(Life::Base(_)))
     }
     pub const fn is_outpost(&self) -> bool {
-        match self.life {
-            Some(Life::OutPost(_)) => true,
-            _ => false,
-        }
+        matches!(self.life, Some(Life::OutPost(_)))
     }
 }
 
-impl Display for Card {
+impl Display for CardDef {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let actions = &self.actions;
         for (i, action) in actions.iter().enumerate() {
@@ -68,95 +98,87 @@ impl Display for Card {
     }
 }
 
-impl Card {
-    /// Creates a [CardWidget] from a [Card]
-    /// Remark: [CardWidget] implements the [Widget] trait and can be displayed
-    pub const fn widget<'a>(&'a self) -> CardWidget<'a> {
-        CardWidget {
-            card: self,
-            selected: false,
-        }
-    }
+impl CardDef {
     /// Creates an iterator over [Action] from a [Card]
     pub fn iter(&self) -> Iter<'_, Action> {
         self.actions.iter()
     }
-    pub fn initials<'a>(&'a self) -> impl Display {
+    pub const fn name(&self) -> &'static str {
+        self.name
+    }
+    /// The definition of the [Card], its copies differ only by their [CardId]
+    pub const fn def_id(&self) -> DefId {
+        DefId(self.name)
+    }
+    pub fn initials(&self) -> impl Display {
         CardInitials(self.name)
     }
 }
 
-pub struct CardInitials<'a>(&'a str);
-impl Display for CardInitials<'_> {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        for s in self.0.split(' ') {
-            write!(f, "{}.", s.chars().nth(0).unwrap())?;
-        }
-        Ok(())
+/// A copy of a card in a [Game](crate::game::Game), it is cheap to copy
+///
+/// The fields of its [CardDef] are reached through [Deref].
+#[derive(Debug, Clone, Copy)]
+pub struct Card {
+    /// Identity of this copy of the [Card] in its game
+    pub id: CardId,
+    def: &'static CardDef,
+}
+
+impl Card {
+    /// A new copy of a shared definition
+    pub const fn new(def: &'static CardDef) -> Self {
+        Self { id: CardId(0), def }
+    }
+    /// The definition shared by the copies of the [Card]
+    pub const fn def(&self) -> &'static CardDef {
+        self.def
+    }
+    /// A [Card] seen from its back, its definition and its id are unknown
+    pub fn hidden() -> Self {
+        Self::default()
+    }
+    /// The [Card] is seen from its back, see [Card::hidden]
+    pub fn is_hidden(&self) -> bool {
+        self.name().is_empty()
     }
 }
 
-#[derive(Debug)]
-pub struct CardWidget<'a> {
-    selected: bool,
-    card: &'a Card,
+impl Default for Card {
+    fn default() -> Self {
+        static EMPTY: CardDef = CardDef::DEFAULT;
+        Self::new(&EMPTY)
+    }
 }
 
-impl CardWidget<'_> {
-    pub fn selected(self) -> Self {
-        Self {
-            selected: true,
-            ..self
-        }
+impl Deref for Card {
+    type Target = CardDef;
+
+    fn deref(&self) -> &Self::Target {
+        self.def
     }
-    pub fn set_selection(self, selected: bool) -> Self {
-        Self { selected, ..self }
+}
+
+/// A [Card] is hashed by its id and its name, not by its whole definition
+impl Hash for Card {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.id.hash(state);
+        self.name().hash(state);
+    }
+}
+
+impl Display for Card {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.def.fmt(f)
     }
 }
 
-impl<'a> Widget for CardWidget<'a> {
-    fn render(self, area: Rect, buf: &mut Buffer)
-    where
-        Self: Sized,
-    {
-        let card = self.card;
-        let gold_string = if card.gold == 0 {
-            String::from("")
-        } else {
-            format!("{}", Action::Gold(card.gold))
-        };
-        let life_string = match card.life {
-            Some(Life::Base(i)) => format!("{i}{}", BASE_STR),
-            Some(Life::OutPost(i)) => format!("{i}{}", OUTPOST_STR),
-            None => format!(""),
-        };
-        let name_string =
-            if card.name.len() + life_string.chars().count() + 2 >= area.width as usize{
-                format!("{}", card.initials())
-            } else {
-                String::from(card.name)
-            };
-        Paragraph::new(format!("{}", self.card))
-            .centered()
-            .style(Style::new())
-            .centered()
-            .block(
-                Block::bordered()
-                    .border_type(if self.selected {
-                        BorderType::Double
-                    } else {
-                        BorderType::Rounded
-                    })
-                    .title_top(Line::from(gold_string).right_aligned())
-                    .title_bottom(life_string)
-                    .title_bottom(Line::from(name_string).right_aligned())
-                    .title_top(Line::from(format!("{}", card.faction)).left_aligned())
-                    .border_style(if self.selected {
-                        Style::new().green()
-                    } else {
-                        Style::new()
-                    }),
-            )
-            .render(area, buf);
+pub struct CardInitials<'a>(&'a str);
+impl Display for CardInitials<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for s in self.0.split(' ') {
+            write!(f, "{}.", s.chars().nth(0).unwrap())?;
+        }
+        Ok(())
     }
 }
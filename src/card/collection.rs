@@ -1,23 +1,72 @@
 use crate::{
     action::{Action, ActionLoc, Condition, Data},
-    card::{Card, Life},
+    card::{Card, CardDef, Life},
     faction::{Faction, Factions},
 };
 
 use Action::*;
 use Faction::*;
-impl Card {
-    pub const EMPTY_SHIP: Self = Card::DEFAULT;
+use std::sync::LazyLock;
+
+/// Defines `Card::$name()`, a copy of the definition built once by `CardDef::$name()`,
+/// and [Card::collection] with every one of them
+macro_rules! shared {
+    ($($name:ident),* $(,)?) => {
+        impl Card {
+            $(
+                pub fn $name() -> Self {
+                    static DEF: LazyLock<CardDef> = LazyLock::new(CardDef::$name);
+                    Card::new(&DEF)
+                }
+            )*
+            /// Number of definitions in the [collection](Card::collection)
+            pub const NB_DEFINITIONS: usize = [$(stringify!($name)),*].len();
+            /// A copy of every definition, always in the same order
+            pub fn collection() -> [Card; Self::NB_DEFINITIONS] {
+                [$(Card::$name()),*]
+            }
+        }
+    };
+}
+
+shared!(
+    viper,
+    scout,
+    explorer,
+    cutter,
+    embassy_yacht,
+    trading_post,
+    barter_world,
+    trade_bot,
+    missile_bot,
+    battle_station,
+    supply_bot,
+    brain_world,
+    corvette,
+    federation_shuttle,
+    imperial_fighter,
+    imperial_frigate,
+    survey_ship,
+    blob_fighter,
+    battle_pod,
+    trade_pod,
+    blob_wheel,
+    ram,
+    blob_destroyer,
+);
+
+impl CardDef {
+    pub const EMPTY_SHIP: Self = CardDef::DEFAULT;
     pub const fn ship() -> Self {
         Self::EMPTY_SHIP
     }
     pub const fn outpost() -> Self {
-        let mut outpost = Card::ship();
+        let mut outpost = CardDef::ship();
         outpost.life = Some(Life::OutPost(0));
         outpost
     }
     pub const fn base() -> Self {
-        let mut base = Card::ship();
+        let mut base = CardDef::ship();
         base.life = Some(Life::Base(0));
         base
     }
@@ -59,7 +108,7 @@ impl Card {
         self
     }
     pub fn when_faction_played(mut self, action: Action) -> Self {
-        if let Some(my_faction) = self.faction.first().clone().cloned() {
+        if let Some(my_faction) = self.faction.first().cloned() {
             self.actions.push(Complex {
                 cond: Condition::FactionPlayed(Factions::new(vec![my_faction.clone()])),
                 action: vec![action],
@@ -296,6 +345,7 @@ impl Card {
         Self::ship()
             .costing(4)
             .with_name("Blob Destroyer")
+            .with_faction(Blob)
             .with_action(Attack(6))
             .when_faction_played(Action::Discard(2))
     }
@@ -0,0 +1,288 @@
+//! Line commands to play a [Game] from scripts, without the terminal
+//!
+//! The cards are named by their index in the decks of the current player,
+//! the commands are turned into [Event]s naming the cards by their id:
+//! ```text
+//! play 0
+//! buy 2
+//! choose hand:0 discard:3
+//! show
+//! end
+//! ```
+
+use crate::{
+    deck::Deck,
+    event::Event,
+    game::Game,
+    selection::{GamePosition, Location},
+    state::State,
+};
+use std::{
+    fmt::Display,
+    io::{BufRead, Write},
+    str::FromStr,
+};
+
+/// Help of the commands, printed by `help`
+pub const HELP: &str = "\
+buy <index>          buy a card of the shop
+explorer             buy an explorer
+play <index>         play a card of the hand
+play all             play every card of the hand
+activate <index>     activate a played card
+choose <loc:index>*  choose cards, for example `choose hand:0 discard:3`
+attack               attack the opponent
+end                  end the turn
+undo                 undo the last event of the turn
+show                 show the whole game
+help                 show this help";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Command {
+    Buy(usize),
+    Explorer,
+    Play(usize),
+    PlayAll,
+    Activate(usize),
+    /// Cards of the current player or of the shop
+    Choose(Vec<(Location, usize)>),
+    Attack,
+    End,
+    Undo,
+    Show,
+    Help,
+}
+
+impl FromStr for Command {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        use Command::*;
+        let mut words = s.split_whitespace();
+        let index = |word: Option<&str>| match word {
+            Some(word) => word
+                .parse::<usize>()
+                .map_err(|_| format!("'{word}' is not an index")),
+            None => Err(String::from("missing index")),
+        };
+        let command = match words.next() {
+            Some("buy") => Buy(index(words.next())?),
+            Some("explorer") => Explorer,
+            Some("play") => match words.next() {
+                Some("all") => PlayAll,
+                word => Play(index(word)?),
+            },
+            Some("activate") => Activate(index(words.next())?),
+            Some("choose") => {
+                let cards = words.map(|word| {
+                    let (loc, i) = word
+                        .split_once(':')
+                        .ok_or_else(|| format!("'{word}' is not a card 'location:index'"))?;
+                    Ok((loc.parse()?, index(Some(i))?))
+                });
+                return cards.collect::<Result<_, String>>().map(Choose);
+            }
+            Some("attack") => Attack,
+            Some("end") => End,
+            Some("undo") => Undo,
+            Some("show") => Show,
+            Some("help") => Help,
+            Some(word) => return Err(format!("unknown command '{word}', try 'help'")),
+            None => return Err(String::from("empty command")),
+        };
+        match words.next() {
+            Some(word) => Err(format!("unexpected '{word}' after '{s}'")),
+            None => Ok(command),
+        }
+    }
+}
+
+impl Command {
+    /// The [Event] of a command, or `None` when it does not play
+    pub fn event(&self, game: &Game) -> Result<Option<Event>, String> {
+        use Command::*;
+        let id = |loc: Location, index: usize| {
+            let position = GamePosition {
+                player: game.current_player,
+                loc,
+                index,
+            };
+            game.card_at(&position)
+                .map(|card| card.id)
+                .ok_or_else(|| format!("no card at index {index} of the {loc}"))
+        };
+        let event = match self {
+            Buy(index) => Event::Buy(id(Location::Shop, *index)?),
+            Explorer => Event::BuyExplorer,
+            Play(index) => Event::Play(id(Location::Hand, *index)?),
+            PlayAll => Event::PlayAll,
+            Activate(index) => Event::Activate(id(Location::Played, *index)?),
+            Choose(cards) => Event::Choose(
+                cards
+                    .iter()
+                    .map(|(loc, index)| id(*loc, *index))
+                    .collect::<Result<_, _>>()?,
+            ),
+            Attack => Event::Attack,
+            End => Event::NextTurn,
+            Undo | Show | Help => return Ok(None),
+        };
+        Ok(Some(event))
+    }
+}
+
+/// Executes a line, returns the text to print
+///
+/// A played [Event] answers the [summary] of the [Game].
+pub fn execute(game: &mut Game, line: &str) -> Result<String, String> {
+    let command: Command = line.parse()?;
+    match command.event(game)? {
+        Some(event) => match game.apply_event(event.clone()) {
+            Ok(()) => Ok(summary(game).to_string()),
+            Err(rejected) => Err(format!(
+                "'{event}' is rejected while {}: {rejected}",
+                StateText(game)
+            )),
+        },
+        None => match command {
+            Command::Undo => match game.undo() {
+                Ok(()) => Ok(summary(game).to_string()),
+                Err(rejected) => Err(rejected.to_string()),
+            },
+            Command::Show => Ok(show(game).to_string()),
+            _ => Ok(String::from(HELP)),
+        },
+    }
+}
+
+/// Executes the lines of `input` and writes the answers to `output`,
+/// the failures are written `error: ...`
+///
+/// The empty lines and the lines starting with `#` are skipped,
+/// returns the number of failed commands.
+pub fn run(game: &mut Game, input: impl BufRead, mut output: impl Write) -> std::io::Result<usize> {
+    let mut failures = 0;
+    writeln!(output, "seed {}", game.seed)?;
+    for line in input.lines() {
+        let line = line?;
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        match execute(game, line) {
+            Ok(text) => writeln!(output, "{text}")?,
+            Err(error) => {
+                failures += 1;
+                writeln!(output, "error: {error}")?;
+            }
+        }
+        output.flush()?;
+    }
+    Ok(failures)
+}
+
+/// One line about the turn and the resources of the players, for example
+/// `turn 2, 1st player playing: 3 gold, 2 attack, 50 authority, opponent 50 authority`
+pub fn summary(game: &Game) -> impl Display {
+    Summary(game)
+}
+
+/// The whole [Game] as seen by the current player
+pub fn show(game: &Game) -> impl Display {
+    Show(game)
+}
+
+struct StateText<'a>(&'a Game);
+impl Display for StateText<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let game = self.0;
+        match &game.state {
+            State::Playing => write!(f, "{} player playing", game.current_player),
+            State::Scraping { nb, loc } => write!(
+                f,
+                "{} player scraping up to {nb} cards {}",
+                game.current_player,
+                loc.phrase()
+            ),
+            State::Discarding { nb, .. } => {
+                write!(f, "{} player discarding {nb} cards", game.current_player)
+            }
+            State::Copy => write!(f, "{} player copying a ship", game.current_player),
+            State::WonBy(winner) => write!(f, "won by the {winner} player"),
+        }
+    }
+}
+
+struct Summary<'a>(&'a Game);
+impl Display for Summary<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let game = self.0;
+        let player = &game[game.current_player];
+        let opponent = &game[game.current_player.other()];
+        write!(
+            f,
+            "turn {}, {}: {} gold, {} attack, {} authority, opponent {} authority",
+            game.turn,
+            StateText(game),
+            player.gold,
+            player.attack,
+            player.authority,
+            opponent.authority
+        )
+    }
+}
+
+/// `[0] Scout, [1] Viper` or `empty`, with the prices in the shop
+struct Cards<'a>(&'a Deck, bool);
+impl Display for Cards<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let Cards(deck, prices) = self;
+        if deck.is_empty() {
+            return write!(f, "empty");
+        }
+        for (i, card) in deck.iter().enumerate() {
+            if i != 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "[{i}] {}", card.name())?;
+            if *prices {
+                write!(f, " {} gold", card.gold)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+struct Show<'a>(&'a Game);
+impl Display for Show<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let game = self.0;
+        writeln!(f, "turn {}, {}", game.turn, StateText(game))?;
+        let explorer = game.explorer.get(0).map_or(0, |card| card.gold);
+        writeln!(f, "shop: {}", Cards(&game.shop, true))?;
+        writeln!(f, "explorer: {explorer} gold")?;
+        for (id, who) in [
+            (game.current_player, "you"),
+            (game.current_player.other(), "opponent"),
+        ] {
+            let player = &game[id];
+            writeln!(
+                f,
+                "{who} ({id}): {} gold, {} attack, {} authority",
+                player.gold, player.attack, player.authority
+            )?;
+            if id == game.current_player {
+                writeln!(f, "  hand: {}", Cards(&player.hand, false))?;
+            } else {
+                writeln!(f, "  hand: {} cards", player.hand.len())?;
+            }
+            writeln!(f, "  played: {}", Cards(&player.played, false))?;
+            writeln!(f, "  discard: {}", Cards(&player.discard, false))?;
+            write!(f, "  draw pile: {} cards", player.draw_pile.len())?;
+            if id == game.current_player {
+                writeln!(f)?;
+            }
+        }
+        Ok(())
+    }
+}
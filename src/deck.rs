@@ -1,37 +1,30 @@
 use crate::{
-    action::{ATTACK_STR, AUTHORITY_STR, Action, GOLD_STR},
-    card::Card,
+    action::Action,
+    card::{Card, CardId},
     faction::Faction,
-    game::Game,
-    player_id::PlayerId,
-    selection::{GamePosition, Location},
-};
-use ratatui::{
-    layout::{Constraint, Direction, Layout, Margin},
-    prelude::{Buffer, Rect},
-    style::{Color, Style, Stylize},
-    text::Line,
-    widgets::{Block, BorderType, Clear, Paragraph, Widget},
+    game::GameRng,
+    theme::{Symbols, symbols},
 };
+use rand::RngExt;
 use std::fmt::Display;
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Hash)]
 pub struct Deck(Vec<Card>);
 
 impl Deck {
     pub const EMPTY: Self = Self(Vec::new());
 
     /// Remove a random card from a [Deck]
-    /// return Err(())         if the deck is empty
-    /// return Ok(random_card) otherwise
+    /// return None              if the deck is empty
+    /// return Some(random_card) otherwise
     ///
     /// Remark: we use a uniform distribution
-    pub fn remove_random(&mut self) -> Result<Card, ()> {
+    pub fn remove_random(&mut self, rng: &mut GameRng) -> Option<Card> {
         if self.is_empty() {
-            return Err(());
+            None
         } else {
-            let index = rand::random_range(0..self.len());
-            Ok(self.remove(index))
+            let index = rng.random_range(0..self.len());
+            Some(self.remove(index))
         }
     }
     /// Remove a [Card] from a [Deck] at a spefic index
@@ -54,6 +47,24 @@ impl Deck {
     pub fn pick_at(&mut self, index: usize, from: &mut Deck) {
         self.0.push(from.0.remove(index))
     }
+    pub fn get(&self, index: usize) -> Option<&Card> {
+        self.0.get(index)
+    }
+    /// Index of the [Card] with a given [CardId] if it is in the [Deck]
+    pub fn position(&self, id: CardId) -> Option<usize> {
+        self.0.iter().position(|card| card.id == id)
+    }
+    /// Gives the next ids to the cards of a [Deck]
+    pub fn number(&mut self, next_id: &mut u32) {
+        for card in &mut self.0 {
+            card.id = CardId(*next_id);
+            *next_id += 1;
+        }
+    }
+    /// Turns every [Card] of the [Deck] to its back, see [Card::hidden]
+    pub fn hide(&mut self) {
+        self.0.fill(Card::hidden());
+    }
     pub fn len(&self) -> usize {
         let Deck(cards) = self;
         cards.len()
@@ -103,16 +114,6 @@ impl Deck {
         Self(deck)
     }
 
-    pub fn widget<'a, 'b>(&'a self) -> DeckWidget<'a, 'b> {
-        DeckWidget {
-            name: "",
-            hidden: false,
-            deck: self,
-            selection: vec![],
-            max_cols: None,
-        }
-    }
-
     pub fn iter(&self) -> std::slice::Iter<'_, Card> {
         self.0.iter()
     }
@@ -155,15 +156,12 @@ impl DeckStats {
             Attack(i) => self.attack += *i as f32,
             Authority(i) => self.authority += *i as f32,
             Discard(i) => self.discard += *i as f32,
-            Scrap { loc, nb } => self.scrap += *nb as f32,
+            Scrap { nb, .. } => self.scrap += *nb as f32,
             Draw(i) => self.draw += *i as f32,
             OpponentDiscard(i) => self.opponentdiscard += *i as f32,
-            Complex {
-                cond: condition,
-                action: result,
-            } => (),
+            Complex { .. } => (),
             Copy => (),
-            Or(action, action1) => (),
+            Or(..) => (),
         }
         self
     }
@@ -172,21 +170,29 @@ impl DeckStats {
         self
     }
 
+    /// The means are 0 for an empty deck
+    const fn mean(&self, total: f32) -> f32 {
+        if self.nb_cards == 0. {
+            0.
+        } else {
+            total / self.nb_cards
+        }
+    }
     pub const fn mean_attack(&self) -> f32 {
-        self.attack / self.nb_cards
+        self.mean(self.attack)
     }
     pub const fn mean_gold(&self) -> f32 {
-        self.gold / self.nb_cards
+        self.mean(self.gold)
     }
     pub const fn mean_authority(&self) -> f32 {
-        self.authority / self.nb_cards
+        self.mean(self.authority)
     }
 }
 
 impl std::ops::Sub<DeckStats> for DeckStats {
     type Output = DeckStats;
 
-    fn sub(self, rhs: DeckStats) -> Self::Output {
+    fn sub(self, _rhs: DeckStats) -> Self::Output {
         // todo
         self
     }
@@ -194,9 +200,15 @@ impl std::ops::Sub<DeckStats> for DeckStats {
 
 impl Display for DeckStats {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let Symbols {
+            gold,
+            attack,
+            authority,
+            ..
+        } = symbols();
         write!(
             f,
-            "{:.2}{GOLD_STR}\n{:.2}{ATTACK_STR}\n{:.2}{AUTHORITY_STR}\n{} cards",
+            "{:.2}{gold}\n{:.2}{attack}\n{:.2}{authority}\n{} cards",
             self.mean_gold(),
             self.mean_attack(),
             self.mean_authority(),
@@ -217,116 +229,3 @@ impl std::ops::IndexMut<usize> for Deck {
         &mut self.0[index]
     }
 }
-
-pub struct DeckWidget<'a, 'b> {
-    name: &'b str,
-    selection: Vec<usize>,
-    hidden: bool,
-    max_cols: Option<usize>,
-    deck: &'a Deck,
-}
-
-impl<'a, 'b> DeckWidget<'a, 'b> {
-    pub fn hidden(self) -> Self {
-        Self {
-            hidden: true,
-            ..self
-        }
-    }
-    pub fn set_name<'c>(self, name: &'c str) -> DeckWidget<'a, 'c> {
-        DeckWidget { name: name, ..self }
-    }
-    pub fn set_max_cols(self, max_cols: usize) -> DeckWidget<'a, 'b> {
-        DeckWidget {
-            max_cols: Some(max_cols),
-            ..self
-        }
-    }
-    pub fn set_selection(mut self, game: &Game, loc: Location, playerid: PlayerId) -> Self {
-        use Location::*;
-        pub fn get_index(pos: &GamePosition, loc: Location, playerid: PlayerId) -> Option<usize> {
-            if (pos.player == playerid || pos.loc == Shop || pos.loc == Explorer) && pos.loc == loc
-            {
-                Some(pos.index)
-            } else {
-                None
-            }
-        }
-        if let Some(i) = get_index(&game.position, loc, playerid) {
-            self.selection.push(i)
-        }
-        for select in &game.selection {
-            if let Some(i) = get_index(select, loc, playerid) {
-                self.selection.push(i)
-            }
-        }
-        self
-    }
-}
-
-impl DeckWidget<'_, '_> {
-    /// Renders a Deck
-    ///
-    /// If this Deck necessitate a popup it will return [true]
-    /// else it returns [fals€]
-    pub fn render(mut self, mut area: Rect, buf: &mut Buffer) -> bool
-    where
-        Self: Sized,
-    {
-        let mut need_popup = false;
-        if self.hidden && !self.selection.is_empty() {
-            area = buf.area;
-            self.hidden = false;
-            Clear.render(area, buf);
-            need_popup = true;
-        }
-        Block::bordered()
-            .title_top(Line::from(self.name).left_aligned())
-            .border_type(if !self.selection.is_empty() {
-                BorderType::Plain
-            } else {
-                BorderType::LightDoubleDashed
-            })
-            .border_style(if !self.selection.is_empty() {
-                Style::new().blue()
-            } else {
-                Style::new()
-            })
-            .render(area, buf);
-        let area = area.inner(Margin::new(1, 1));
-        if self.hidden {
-            Paragraph::new(format!("{}", self.deck.get_stats()))
-                .centered()
-                .render(area, buf);
-            return false;
-        }
-
-        let layout: Vec<Rect> = if let Some(max_cols) = self.max_cols {
-            let col_constraints = (0..max_cols).map(|_| Constraint::Fill(1));
-            let row_constraints =
-                (0..(self.deck.len() / max_cols + 1)).map(|_| Constraint::Fill(1));
-            let horizontal = Layout::horizontal(col_constraints).spacing(1);
-            let vertical = Layout::vertical(row_constraints).spacing(1);
-
-            let rows = vertical.split(area);
-            rows.iter()
-                .flat_map(|&row| horizontal.split(row).to_vec())
-                .collect()
-        } else {
-            Layout::default()
-                .direction(Direction::Horizontal)
-                .constraints(vec![Constraint::Fill(1); self.deck.len()])
-                .split(area)
-                .into_iter()
-                .map(|r| r.clone())
-                .collect()
-        };
-
-        for (i, card) in (0..layout.len()).zip(self.deck.iter()) {
-            card.widget()
-                .set_selection(self.selection.contains(&i))
-                .render(layout[i], buf);
-        }
-        need_popup
-    }
-}
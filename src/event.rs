@@ -1,19 +1,25 @@
-use crate::selection::GamePosition;
+use crate::card::CardId;
+use rand::{Rng, RngExt};
+use std::fmt::Display;
 
-#[derive(Debug, Clone)]
+/// Something done by the current player
+///
+/// The cards are named by their [CardId], which do not change when other cards move.
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Event {
-    /// Buy a card from the shop at a given index
-    Buy(usize),
+    /// Buy a card of the shop
+    Buy(CardId),
     /// Buy an explorer
     BuyExplorer,
-    /// Buy a card at a given index in the hand [Deck] of a [Player]
-    Play(usize),
-    /// Activate a card at a given index in the played [Deck]
-    /// of a [Player]
-    Activate(usize),
-    /// Choose multiple cards anywhere in the [Game]
+    /// Play a card of the hand of the current [Player](crate::player::Player)
+    Play(CardId),
+    /// Play every card of the hand of the current [Player](crate::player::Player)
+    PlayAll,
+    /// Activate a card played by the current [Player](crate::player::Player)
+    Activate(CardId),
+    /// Choose multiple cards anywhere in the [Game](crate::game::Game)
     /// for any action
-    Choose(Vec<GamePosition>),
+    Choose(Vec<CardId>),
     /// Triggers an Attack against the opponent
     Attack,
     /// Go to the next turn
@@ -21,21 +27,111 @@ pub enum Event {
 }
 
 impl Event {
-    pub fn random() -> Self {
+    pub fn random<R: Rng>(rng: &mut R) -> Self {
         use Event::*;
-        match rand::random_range(0..6) {
-            0 => Buy(rand::random_range(0..5)),
+        // A bit more than the cards of a new game
+        let id = |rng: &mut R| CardId(rng.random_range(0..64));
+        match rng.random_range(0..8) {
+            0 => Buy(id(rng)),
             1 => BuyExplorer,
-            2 => Play(rand::random_range(0..5)),
-            3 => Activate(rand::random_range(0..15)),
-            4 => Choose(
-                (0..rand::random_range(0..2))
-                    .map(|_| GamePosition::random())
-                    .collect(),
-            ),
+            2 => Play(id(rng)),
+            3 => Activate(id(rng)),
+            4 => Choose((0..rng.random_range(0..2)).map(|_| id(rng)).collect()),
             5 => Attack,
             6 => NextTurn,
+            7 => PlayAll,
             _ => unreachable!(),
         }
     }
 }
+
+/// Writes an [Event] on a single line, for example
+/// `buy #2`, `play #40` or `choose #41 #12`
+impl Display for Event {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        use Event::*;
+        match self {
+            Buy(id) => write!(f, "buy {id}"),
+            BuyExplorer => write!(f, "explorer"),
+            Play(id) => write!(f, "play {id}"),
+            PlayAll => write!(f, "play all"),
+            Activate(id) => write!(f, "activate {id}"),
+            Choose(ids) => {
+                write!(f, "choose")?;
+                for id in ids {
+                    write!(f, " {id}")?;
+                }
+                Ok(())
+            }
+            Attack => write!(f, "attack"),
+            NextTurn => write!(f, "end"),
+        }
+    }
+}
+
+/// Parses an [Event] written by its [Display] implementation
+impl std::str::FromStr for Event {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        use Event::*;
+        let mut words = s.split_whitespace();
+        let id = |word: Option<&str>| match word {
+            Some(word) => word.parse::<CardId>(),
+            None => Err(String::from("missing card id")),
+        };
+        let event = match words.next() {
+            Some("buy") => Buy(id(words.next())?),
+            Some("explorer") => BuyExplorer,
+            Some("play") => match words.next() {
+                Some("all") => PlayAll,
+                word => Play(id(word)?),
+            },
+            Some("activate") => Activate(id(words.next())?),
+            Some("choose") => {
+                return Ok(Choose(
+                    words.map(str::parse).collect::<Result<Vec<_>, _>>()?,
+                ));
+            }
+            Some("attack") => Attack,
+            Some("end") => NextTurn,
+            Some(word) => return Err(format!("unknown event '{word}'")),
+            None => return Err(String::from("empty event")),
+        };
+        match words.next() {
+            Some(word) => Err(format!("unexpected '{word}' after '{event}'")),
+            None => Ok(event),
+        }
+    }
+}
+
+/// Why an [Event] or an undo was rejected, the [Game](crate::game::Game) did not change
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Rejected {
+    /// The [Event] is not played in the current [State](crate::state::State)
+    WrongState,
+    /// The card is not where the [Event] needs it
+    NoSuchCard(CardId),
+    /// The current player cannot pay for the card
+    NotEnoughGold,
+    /// The chosen cards do not match what the action asks for
+    WrongChoice,
+    /// Activating a played card is not implemented yet
+    Unsupported,
+    /// No accepted [Event] to undo in the current turn
+    NothingToUndo,
+}
+
+impl Display for Rejected {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        use Rejected::*;
+        match self {
+            WrongState => write!(f, "not playable now"),
+            NoSuchCard(id) => write!(f, "no card {id} here"),
+            NotEnoughGold => write!(f, "not enough gold"),
+            WrongChoice => write!(f, "not the cards asked for"),
+            Unsupported => write!(f, "not implemented yet"),
+            NothingToUndo => write!(f, "nothing to undo in this turn"),
+        }
+    }
+}
@@ -1,6 +1,11 @@
+#[cfg(feature = "tui")]
+use crate::theme::Palette;
+use crate::theme::symbols;
+#[cfg(feature = "tui")]
+use ratatui::style::Color;
 use std::{fmt::Display, slice::Iter};
 
-#[derive(Debug, Default)]
+#[derive(Debug, Clone, Default, Hash)]
 pub struct Factions(Vec<Faction>);
 
 impl Factions {
@@ -19,7 +24,7 @@ impl std::ops::Index<usize> for Factions {
     type Output = Faction;
 
     fn index(&self, index: usize) -> &Self::Output {
-        todo!()
+        &self.0[index]
     }
 }
 
@@ -38,6 +43,19 @@ impl Factions {
     }
 }
 impl Faction {
+    pub const fn name(&self) -> &'static str {
+        match self {
+            Faction::Blob => "Blob",
+            Faction::Trade => "Trade Federation",
+            Faction::Star => "Star Empire",
+            Faction::Machine => "Machine Cult",
+        }
+    }
+    /// Colour of the [Faction] in the current [Palette]
+    #[cfg(feature = "tui")]
+    pub fn color(&self) -> Color {
+        Palette::current().faction(self)
+    }
     pub const fn to_usize(&self) -> usize {
         match self {
             Faction::Blob => 0,
@@ -47,7 +65,7 @@ impl Faction {
         }
     }
 }
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
 pub enum Faction {
     Blob,
     Trade,
@@ -55,13 +73,9 @@ pub enum Faction {
     Machine,
 }
 
+/// Writes the symbol of a [Faction] in the current [Theme](crate::theme::Theme)
 impl Display for Faction {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self {
-            Faction::Blob => write!(f, "🐉"),
-            Faction::Trade => write!(f, "🏦"),
-            Faction::Star => write!(f, "🌟"),
-            Faction::Machine => write!(f, "⚙️"),
-        }
+        write!(f, "{}", symbols().factions[self.to_usize()])
     }
 }
@@ -0,0 +1,186 @@
+//! Reproducible fuzzing of a [Game]
+//!
+//! A fuzz [Case] is a seed with a sequence of random [Step]s generated from it,
+//! the [invariants](crate::invariants) are checked after every step.
+//! A failing case is shrunk to a minimal one, written like a [Record](crate::record::Record):
+//! ```text
+//! seed 42
+//! play #40
+//! end
+//! ```
+
+#[cfg(feature = "tui")]
+use crate::tui::view::{Gesture, View};
+use crate::{event::Event, game::Game, game::GameRng, invariants, record};
+use rand::SeedableRng;
+use std::{
+    fmt::Display,
+    panic::{AssertUnwindSafe, catch_unwind},
+    str::FromStr,
+};
+
+/// What the [Step]s of a fuzzer are played on, holding a [Game]
+pub trait Target {
+    /// Starts from the [Game] created from a seed
+    fn with_seed(seed: u64) -> Self;
+    /// Checks the [invariants] after every step
+    fn check(&self) -> Result<(), String>;
+}
+
+impl Target for Game {
+    fn with_seed(seed: u64) -> Self {
+        Game::with_seed(seed)
+    }
+    fn check(&self) -> Result<(), String> {
+        invariants::check(self)
+    }
+}
+
+/// A [Game] played from the terminal, the cursor stays in the [Game]
+#[cfg(feature = "tui")]
+impl Target for (Game, View) {
+    fn with_seed(seed: u64) -> Self {
+        (Game::with_seed(seed), View::default())
+    }
+    fn check(&self) -> Result<(), String> {
+        invariants::check(&self.0)?;
+        self.1.check(&self.0)
+    }
+}
+
+/// Something done on a [Game] by a fuzzer
+pub trait Step: Clone + Display + FromStr<Err = String> {
+    type Target: Target;
+    fn random(rng: &mut GameRng) -> Self;
+    fn apply(&self, target: &mut Self::Target);
+}
+
+impl Step for Event {
+    type Target = Game;
+    fn random(rng: &mut GameRng) -> Self {
+        Event::random(rng)
+    }
+    fn apply(&self, game: &mut Game) {
+        let _ = game.apply_event(self.clone());
+    }
+}
+
+#[cfg(feature = "tui")]
+impl Step for Gesture {
+    type Target = (Game, View);
+    fn random(rng: &mut GameRng) -> Self {
+        Gesture::random(rng)
+    }
+    fn apply(&self, (game, view): &mut (Game, View)) {
+        view.gesture(game, *self);
+    }
+}
+
+/// The [Step]s played on the [Game] created from a seed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Case<S> {
+    pub seed: u64,
+    pub steps: Vec<S>,
+}
+
+/// A [Step] of a [Case] that panicked or broke an invariant
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Failure {
+    /// Index of the step
+    pub step: usize,
+    pub error: String,
+}
+
+impl Display for Failure {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "step {}: {}", self.step, self.error)
+    }
+}
+
+impl<S: Step> Case<S> {
+    /// `nb` random steps, the same ones for the same `seed`
+    pub fn generate(seed: u64, nb: usize) -> Self {
+        let mut rng = GameRng::seed_from_u64(seed);
+        Self {
+            seed,
+            steps: (0..nb).map(|_| S::random(&mut rng)).collect(),
+        }
+    }
+    /// Plays the steps on a new [Game], stops at the first [Failure]
+    pub fn run(&self) -> Result<(), Failure> {
+        let mut target = S::Target::with_seed(self.seed);
+        for (step, s) in self.steps.iter().enumerate() {
+            let result = catch_unwind(AssertUnwindSafe(|| {
+                s.apply(&mut target);
+                target.check()
+            }));
+            let error = match result {
+                Ok(Ok(())) => continue,
+                Ok(Err(error)) => error,
+                Err(panic) => match panic.downcast::<String>() {
+                    Ok(message) => format!("panicked: {message}"),
+                    Err(panic) => match panic.downcast::<&str>() {
+                        Ok(message) => format!("panicked: {message}"),
+                        Err(_) => String::from("panicked"),
+                    },
+                },
+            };
+            return Err(Failure { step, error });
+        }
+        Ok(())
+    }
+    /// Removes the steps not needed for the case to fail
+    ///
+    /// The steps after the failure are dropped, then smaller and smaller chunks
+    /// of steps are removed as long as the case still fails, maybe on another invariant.
+    pub fn shrink(mut self) -> Self {
+        let Err(failure) = self.run() else {
+            return self;
+        };
+        self.steps.truncate(failure.step + 1);
+        let mut chunk = self.steps.len().div_ceil(2);
+        while chunk > 0 {
+            let mut start = 0;
+            while start < self.steps.len() {
+                let mut candidate = self.clone();
+                let end = (start + chunk).min(candidate.steps.len());
+                candidate.steps.drain(start..end);
+                match candidate.run() {
+                    Err(failure) => {
+                        candidate.steps.truncate(failure.step + 1);
+                        self = candidate;
+                    }
+                    Ok(()) => start += chunk,
+                }
+            }
+            chunk /= 2;
+        }
+        self
+    }
+}
+
+/// Generates and runs a [Case] of `nb` steps,
+/// returns the shrunk case when it fails
+pub fn fuzz<S: Step>(seed: u64, nb: usize) -> Result<(), (Case<S>, Failure)> {
+    let case = Case::<S>::generate(seed, nb);
+    case.run().map_err(|_| {
+        let case = case.shrink();
+        let failure = case.run().expect_err("a shrunk case fails");
+        (case, failure)
+    })
+}
+
+impl<S: Display> Display for Case<S> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        record::write_steps(f, self.seed, &self.steps)
+    }
+}
+
+impl<S: FromStr<Err = String>> FromStr for Case<S> {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (seed, steps) = record::parse_steps(s)?;
+        Ok(Self { seed, steps })
+    }
+}
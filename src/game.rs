@@ -1,62 +1,199 @@
 use crate::action::ActionLoc;
-use crate::card::Card;
+use crate::card::{Card, CardId};
 use crate::deck::Deck;
-use crate::event::Event;
-use crate::input::Input;
+use crate::event::{Event, Rejected};
+use crate::log::{LogEntry, Logged};
 use crate::player::Player;
 use crate::player_id::PlayerId;
 use crate::selection::GamePosition;
 use crate::selection::Location;
 use crate::state::State;
-use ratatui::layout::Constraint;
-use ratatui::layout::Layout;
-use ratatui::layout::Margin;
-use ratatui::prelude::Buffer;
-use ratatui::prelude::Rect;
-use ratatui::widgets::Block;
-use ratatui::widgets::Paragraph;
-use ratatui::widgets::Widget;
-use std::fmt::Display;
-
-#[derive(Debug)]
-pub struct Game {
+use rand::rngs::Xoshiro256PlusPlus;
+use rand::{Rng, SeedableRng, TryRng};
+use std::convert::Infallible;
+use std::hash::{Hash, Hasher};
+use std::ops::{Deref, DerefMut};
+
+/// Number of cards on a row of the popup of a hidden deck
+pub const POPUP_COLS: usize = 6;
+
+/// Random number generator used for every random choice of a [Game]
+///
+/// Remark: this generator is portable, two [Game] created with the same
+/// seed evolve the same way on every machine given the same [Event]s.
+/// It counts its draws to be restored from its seed and its number of draws.
+#[derive(Debug, Clone)]
+pub struct GameRng {
+    seed: u64,
+    draws: u64,
+    rng: Xoshiro256PlusPlus,
+}
+
+impl GameRng {
+    /// The generator created from a `seed` after `draws` draws
+    pub fn restore(seed: u64, draws: u64) -> Self {
+        let mut rng = Self::seed_from_u64(seed);
+        for _ in 0..draws {
+            rng.rng.next_u64();
+        }
+        rng.draws = draws;
+        rng
+    }
+    pub const fn seed(&self) -> u64 {
+        self.seed
+    }
+    /// Number of words drawn since the generator was created
+    pub const fn draws(&self) -> u64 {
+        self.draws
+    }
+}
+
+/// Seeded by a `u64`, like [Xoshiro256PlusPlus::seed_from_u64]
+impl SeedableRng for GameRng {
+    type Seed = [u8; 8];
+
+    fn from_seed(seed: Self::Seed) -> Self {
+        Self::seed_from_u64(u64::from_le_bytes(seed))
+    }
+    fn seed_from_u64(seed: u64) -> Self {
+        Self {
+            seed,
+            draws: 0,
+            rng: Xoshiro256PlusPlus::seed_from_u64(seed),
+        }
+    }
+}
+
+/// Draws the same numbers as [Xoshiro256PlusPlus], one word at a time
+impl TryRng for GameRng {
+    type Error = Infallible;
+
+    fn try_next_u32(&mut self) -> Result<u32, Infallible> {
+        self.try_next_u64().map(|word| (word >> 32) as u32)
+    }
+    fn try_next_u64(&mut self) -> Result<u64, Infallible> {
+        self.draws += 1;
+        Ok(self.rng.next_u64())
+    }
+    fn try_fill_bytes(&mut self, dst: &mut [u8]) -> Result<(), Infallible> {
+        rand::rand_core::utils::fill_bytes_via_next_word(dst, || self.try_next_u64())
+    }
+}
+
+/// The cards and the state of a [Game], everything the rules change
+///
+/// Remark: trying an [Event] clones the [Rules] only,
+/// not the historic, the log and the start of the turn of the [Game].
+#[derive(Debug, Clone)]
+pub struct Rules {
+    pub(crate) rng: GameRng,
     pub players: [Player; 2],
     pub deck: Deck,
     pub explorer: Deck,
     pub shop: Deck,
-    pub position: GamePosition,
-    pub selection: Vec<GamePosition>,
+    /// Cards scrapped by the players, out of the game
+    pub scrap_heap: Deck,
     pub state: State,
     pub current_player: PlayerId,
+    /// [CardId] of the next card entering the [Game]
+    pub(crate) next_id: u32,
+    /// Number of the turn being played, starting at 1
+    pub turn: u32,
+}
+
+/// A game with its [Rules] reached through [Deref], and what has been played
+///
+/// Remark: a clone copies the historic, the log and the start of the turn,
+/// a search clones the [Rules] only and plays on them with [Rules::play].
+#[derive(Debug, Clone)]
+pub struct Game {
+    pub seed: u64,
+    pub rules: Rules,
+    /// The [Rules] at the start of the turn,
+    /// with the number of events played and of entries logged before the turn
+    turn_start: Option<(Box<Rules>, usize, usize)>,
+    /// The accepted events, replaying them from the seed gives back the [Game]
     pub historic: Vec<Event>,
+    /// The accepted events with the names of their cards
+    pub log: Vec<LogEntry>,
+}
+
+impl Deref for Game {
+    type Target = Rules;
+    fn deref(&self) -> &Rules {
+        &self.rules
+    }
+}
+impl DerefMut for Game {
+    fn deref_mut(&mut self) -> &mut Rules {
+        &mut self.rules
+    }
+}
+impl Default for Game {
+    fn default() -> Self {
+        Self::new()
+    }
 }
+
 impl Game {
+    /// Creates a new [Game] with a random seed
     pub fn new() -> Self {
-        let current_player = PlayerId::random();
+        Self::with_seed(rand::random())
+    }
+    /// Creates a new [Game] from a `seed`
+    ///
+    /// Two games created with the same `seed` are identical.
+    pub fn with_seed(seed: u64) -> Self {
+        let mut rng = GameRng::seed_from_u64(seed);
+        let current_player = PlayerId::random(&mut rng);
         let mut deck = Deck::starter_complete_deck();
         let mut shop = Deck::EMPTY;
         let mut explorer = Deck::EMPTY;
         explorer.push(Card::explorer());
+        let mut players = [Player::default(), Player::default()];
+        let mut next_id = 0;
+        for cards in [&mut explorer, &mut deck]
+            .into_iter()
+            .chain(players.iter_mut().map(|player| &mut player.draw_pile))
+        {
+            cards.number(&mut next_id);
+        }
         // Here we suppose that the deck have at least 5 elements
         for _ in 0..5 {
-            shop.push(deck.remove_random().unwrap());
+            shop.push(deck.remove_random(&mut rng).unwrap());
         }
-        let mut players = [Player::default(), Player::default()];
         for _ in 0..3 {
-            players[current_player].draw_random_card();
+            players[current_player].draw_random_card(&mut rng);
         }
-        Self {
-            players,
-            deck,
-            explorer,
-            shop,
-            position: GamePosition::default(),
-            current_player,
-            state: State::START_GAME,
-            selection: vec![],
+        let mut game = Self {
+            seed,
+            rules: Rules {
+                rng,
+                players,
+                deck,
+                explorer,
+                shop,
+                scrap_heap: Deck::EMPTY,
+                current_player,
+                next_id,
+                state: State::START_GAME,
+                turn: 1,
+            },
+            turn_start: None,
             historic: vec![],
-        }
+            log: vec![],
+        };
+        game.save_turn_start();
+        game
+    }
+    /// Remembers the [Rules] at the start of the turn to undo its events
+    pub(crate) fn save_turn_start(&mut self) {
+        let start = Box::new(self.rules.clone());
+        self.turn_start = Some((start, self.historic.len(), self.log.len()));
     }
+}
+
+impl Rules {
     /// Pass to the next turn in a [Game]
     /// - ending the turn of a player
     ///   - money set to 0
@@ -95,194 +232,280 @@ impl Game {
                 nb: current.opponent_discard,
                 loc: ActionLoc::Hand,
             };
+            current.opponent_discard = 0;
         } else {
             self.state = State::Playing;
         }
 
         // Make the new current_player draw his hand
-        opponent.draw_hand();
+        opponent.draw_hand(&mut self.rng);
 
         // Change the current player
         self.current_player = self.current_player.other();
+        self.turn += 1;
     }
 
-    /// Get the event from doing an action
-    /// thas usually comes from pressing the enter button.
-    pub fn do_action(&mut self) -> Option<Event> {
+    /// Check if an event is a valid event
+    ///
+    /// todo be more restrictive about what "valid" means
+    pub fn event_is_valid(&self, event: &Event) -> bool {
         use Event::*;
         use State::*;
-        let attack = self.players[self.current_player].attack;
-        match self.state {
-            Playing => {
-                if attack == 0 {
-                    Some(NextTurn)
-                } else {
-                    Some(Attack)
-                }
-            }
-            Scraping { .. } | Discarding { .. } | Copy => Some(Choose(self.selection.clone())),
-            WonBy(_) => None,
-        }
+        matches!(
+            (&self.state, event),
+            (Playing, Buy(_))
+                | (Playing, BuyExplorer)
+                | (Playing, Play(_))
+                | (Playing, PlayAll)
+                | (Playing, Activate(_))
+                | (Playing, Attack)
+                | (Playing, NextTurn)
+                | (Discarding { .. }, Choose(_))
+                | (Scraping { .. }, Choose(_))
+                | (Copy, Choose(_))
+        )
     }
-
-    /// Get the event from doing a selection
-    /// that usually comes from pressing the space bar.
-    pub fn do_selection(&mut self) -> Option<Event> {
-        use Location::*;
-        use State::*;
-        match self.state {
-            Copy | Discarding { .. } | Scraping { .. } => {
-                if let Some(index) = self.selection.iter().position(|pos| pos == &self.position) {
-                    self.selection.remove(index);
-                } else {
-                    self.selection.push(self.position.clone());
+    /// The [Event]s accepted in the current state of the [Game]
+    ///
+    /// Remark: the only choices are the empty one and the choices of a single card,
+    /// activating a played card is rejected until it is implemented.
+    pub fn legal_events(&self) -> Vec<Event> {
+        use Event::*;
+        let player = &self[self.current_player];
+        let ids = |deck: &Deck| deck.iter().map(|card| card.id).collect::<Vec<_>>();
+        let choices = |decks: &[&Deck]| {
+            std::iter::once(Choose(vec![]))
+                .chain(
+                    decks
+                        .iter()
+                        .flat_map(|deck| ids(deck))
+                        .map(|id| Choose(vec![id])),
+                )
+                .collect()
+        };
+        let candidates: Vec<Event> = match &self.state {
+            State::Playing => {
+                let mut events: Vec<_> = ids(&self.shop).into_iter().map(Buy).collect();
+                events.push(BuyExplorer);
+                events.extend(ids(&player.hand).into_iter().map(Play));
+                if !player.hand.is_empty() {
+                    events.push(PlayAll);
                 }
-                return None;
+                if player.attack != 0 {
+                    events.push(Attack);
+                }
+                events.push(NextTurn);
+                events
             }
-            _ => (),
-        }
-        match self.position.loc {
-            Explorer => Some(Event::BuyExplorer),
-            Shop => Some(Event::Buy(self.position.index)),
-            Hand => Some(Event::Play(self.position.index)),
-            Played => Some(Event::Activate(self.position.index)),
-            Discard => None,
-            DrawPile => None,
+            State::Scraping { loc, .. } => {
+                let decks: Vec<_> = [Location::Shop, Location::Hand, Location::Discard]
+                    .into_iter()
+                    .filter(|zone| loc.contains(zone))
+                    .map(|zone| self.zone(self.current_player, zone))
+                    .collect();
+                choices(&decks)
+            }
+            State::Discarding { .. } => choices(&[&player.hand]),
+            State::Copy => choices(&[&player.played]),
+            State::WonBy(_) => vec![],
+        };
+        candidates
+            .into_iter()
+            .filter(|event| self.clone().apply_rules(event.clone()).is_ok())
+            .collect()
+    }
+    /// Applies an [Event] without recording it, unlike [Game::apply_event]
+    ///
+    /// A search plays on clones of the [Rules], cheaper than clones of the [Game].
+    pub fn play(&mut self, event: Event) -> Result<(), Rejected> {
+        if !self.event_is_valid(&event) {
+            return Err(Rejected::WrongState);
         }
+        self.apply_rules(event)
     }
-    /// Given the [Game] state
-    /// returns an [Event] from an [Input]
-    pub fn get_event(&mut self, input: Input) -> Option<Event> {
-        use Input::*;
-        let event = match input {
-            Enter => self.do_action(),
-            Space => self.do_selection(),
-            Left => {
-                if self.position.index == 0 {
-                    self.position.next_left();
-                } else {
-                    self.position.index -= 1;
+}
+
+impl Game {
+    /// Apply an [Event] to the [Game]
+    ///
+    /// The accepted events are recorded in the `historic` and the `log`,
+    /// returns why the event has been rejected when it did not change the [Game].
+    pub fn apply_event(&mut self, event: Event) -> Result<(), Rejected> {
+        let (turn, player) = (self.turn, self.current_player);
+        let (attack, played) = (self[player].attack, self[player].played.len());
+        let mut logged = self.logged(&event);
+        let result = self.rules.play(event.clone());
+        if result.is_ok() {
+            self.historic.push(event.clone());
+            match event {
+                Event::PlayAll => {
+                    let cards = self[player].played.iter().skip(played);
+                    logged.extend(cards.map(|card| Logged::Played(card.name())));
                 }
-                None
-            }
-            Right => {
-                if self.position.index + 1 >= self[self.position.player][self.position.loc].len() {
-                    self.position.next_right();
-                } else {
-                    self.position.index += 1;
+                Event::Attack => {
+                    let authority = self[player.other()].authority;
+                    logged.push(Logged::Attacked { attack, authority });
+                    if let State::WonBy(_) = self.state {
+                        logged.push(Logged::Won);
+                    }
                 }
-                None
-            }
-            Up => {
-                self.position.next_up(&self.current_player);
-                None
+                _ => (),
             }
-            Down => {
-                self.position.next_down(&self.current_player);
-                None
+            let entries = logged.into_iter().map(|logged| LogEntry {
+                turn,
+                player,
+                logged,
+            });
+            self.log.extend(entries);
+            if let Event::NextTurn = event {
+                self.save_turn_start();
             }
-            Other => None,
-        };
-        if self.position.player == self.current_player.other()
-            && self.position.loc == Location::Hand
-        {
-            self.position.loc = Location::Played;
-            self.position.index = 0;
-        }
-        event
-    }
-    pub fn interact(&mut self, input: Input) {
-        if let Some(event) = self.get_event(input) {
-            self.apply_event(event);
         }
+        result
     }
-    /// Check if an event is a valid event
+    /// What an [Event] about to be applied does to the cards, to be logged
     ///
-    /// todo be more restrictive about what "valid" means
-    pub fn event_is_valid(&self, event: &Event) -> bool {
-        use Event::*;
-        use State::*;
-        match (&self.state, event) {
-            (Playing, Buy(_)) => true,
-            (Playing, BuyExplorer) => true,
-            (Playing, Play(_)) => true,
-            (Playing, Activate(_)) => true,
-            (Playing, Attack) => true,
-            (Playing, NextTurn) => true,
-            (Discarding { .. }, Choose(_)) => true,
-            (Copy, Choose(_)) => true,
-            _ => false,
-        }
+    /// Remark: the cards played by [Event::PlayAll] are only known afterwards.
+    fn logged(&self, event: &Event) -> Vec<Logged> {
+        let name = |id: &CardId| {
+            let position = self.position_of(*id)?;
+            self.card_at(&position).map(|card| card.name())
+        };
+        let logged = match event {
+            Event::Buy(id) => self
+                .shop
+                .position(*id)
+                .map(|index| self.shop[index])
+                .map(|card| Logged::Bought {
+                    card: card.name(),
+                    gold: card.gold,
+                }),
+            Event::BuyExplorer => self.explorer.get(0).map(|card| Logged::Bought {
+                card: card.name(),
+                gold: card.gold,
+            }),
+            Event::Play(id) => name(id).map(Logged::Played),
+            Event::Choose(ids) if ids.is_empty() => Some(Logged::ChoseNothing),
+            Event::Choose(ids) => {
+                let chosen = match self.state {
+                    State::Scraping { .. } => Logged::Scrapped,
+                    State::Discarding { .. } => Logged::Discarded,
+                    _ => Logged::Copied,
+                };
+                return ids.iter().filter_map(name).map(chosen).collect();
+            }
+            Event::NextTurn => Some(Logged::EndedTurn),
+            Event::PlayAll | Event::Attack | Event::Activate(_) => None,
+        };
+        logged.into_iter().collect()
     }
-    pub fn apply_event(&mut self, event: Event) {
-        // Verify first that the event is valid given the context of the [Game]
-        if !self.event_is_valid(&event) {
-            self.historic.push(event.clone());
-            return;
-        }
+}
+
+impl Rules {
+    /// Applies the rules of a valid [Event]
+    fn apply_rules(&mut self, event: Event) -> Result<(), Rejected> {
         use Event::*;
         use PlayerId::*;
         use State::*;
         match event {
-            Buy(index) => {
-                let _ = self.players[self.current_player].buy_from_shop(
+            Buy(id) => {
+                let index = self.shop.position(id).ok_or(Rejected::NoSuchCard(id))?;
+                self.players[self.current_player].buy_from_shop(
                     &mut self.deck,
                     &mut self.shop,
                     index,
-                );
+                    &mut self.rng,
+                )
             }
             BuyExplorer => {
-                let _ = self.players[self.current_player].buy_card(Card::explorer());
+                let mut explorer = Card::explorer();
+                explorer.id = CardId(self.next_id);
+                self.players[self.current_player].buy_card(explorer)?;
+                self.next_id += 1;
+                Ok(())
             }
-            Play(index) => {
-                let player = &mut self.players[self.position.player];
-                if !player.hand.is_empty() && (0..player.hand.len()).contains(&index) {
-                    player.play_card(index, &mut self.state);
-
-                    if index == player.hand.len() && !player.hand.is_empty() {
-                        self.position.index = player.hand.len() - 1;
-                    }
-                } else {
-                    self.position.index = 0;
+            Play(id) => {
+                let player = &mut self.players[self.current_player];
+                let index = player.hand.position(id).ok_or(Rejected::NoSuchCard(id))?;
+                player.play_card(index, &mut self.state, &mut self.rng);
+                Ok(())
+            }
+            PlayAll => {
+                let player = &mut self.players[self.current_player];
+                // Stop when a card asks for a choice
+                while !player.hand.is_empty() && matches!(self.state, Playing) {
+                    player.play_card(0, &mut self.state, &mut self.rng);
                 }
+                Ok(())
             }
-            Choose(positions) => match &self.state {
-                Discarding { nb, loc } => {
-                    if positions.len() as u32 > *nb {
-                        return;
+            Choose(ids) => match &self.state {
+                Discarding { nb, .. } => {
+                    if ids.len() as u32 > *nb {
+                        return Err(Rejected::WrongChoice);
                     }
-                    for pos in &self.selection {
-                        self.players[self.current_player].discard_card(pos);
+                    let mut ids = ids;
+                    ids.sort();
+                    ids.dedup();
+                    // Only cards of the hand of the current player
+                    let player = &mut self.players[self.current_player];
+                    if let Some(id) = ids.iter().find(|id| player.hand.position(**id).is_none()) {
+                        return Err(Rejected::NoSuchCard(*id));
+                    }
+                    for id in ids {
+                        player.discard_card(id)?;
                     }
-                    self.selection.clear();
                     self.state = State::Playing;
+                    Ok(())
                 }
                 Scraping { nb, loc } => {
                     // Check that the right number of scraps has been done
-                    if positions.len() as u32 > *nb {
-                        return;
+                    if ids.len() as u32 > *nb {
+                        return Err(Rejected::WrongChoice);
                     }
-                    // Check that the discard has been done at the right place
-                    for pos in &positions {
-                        if !loc.contains(&pos.loc) {
-                            return;
+                    let mut ids = ids;
+                    ids.sort();
+                    ids.dedup();
+                    // Only cards of the shop or of the current player, at the right place
+                    let mut positions = Vec::with_capacity(ids.len());
+                    for id in ids {
+                        match self.position_of(id) {
+                            Some(pos)
+                                if loc.contains(&pos.loc)
+                                    && (pos.loc == Location::Shop
+                                        || pos.player == self.current_player) =>
+                            {
+                                positions.push(pos)
+                            }
+                            Some(_) => return Err(Rejected::WrongChoice),
+                            None => return Err(Rejected::NoSuchCard(id)),
                         }
                     }
+                    positions.sort_by_key(|pos| std::cmp::Reverse(pos.index));
+
+                    // From the last index for the indices to be stable
+                    for pos in positions {
+                        let card = if pos.loc == Location::Shop {
+                            // The shop is refilled from the deck
+                            match self.deck.remove_random(&mut self.rng) {
+                                Some(card) => std::mem::replace(&mut self.shop[pos.index], card),
+                                None => self.shop.remove(pos.index),
+                            }
+                        } else {
+                            self.players[self.current_player][pos.loc].remove(pos.index)
+                        };
+                        self.scrap_heap.push(card);
+                    }
 
-                    // todo fix this
-                    // for pos in &self.selection {
-                    //     self.scrap_card(pos);
-                    // }
-
-                    self.selection.clear();
                     self.state = State::Playing;
+                    Ok(())
                 }
                 Copy => {
-                    if positions.len() != 1 {
-                        return;
+                    if ids.len() != 1 {
+                        return Err(Rejected::WrongChoice);
                     }
+                    Ok(())
                 }
-                _ => (),
+                _ => Err(Rejected::WrongState),
             },
             Attack => {
                 let [fst, snd] = &mut self.players;
@@ -292,255 +515,204 @@ impl Game {
                 };
 
                 let attack = player.attack;
-                if opponent.authority < attack {
+                if opponent.authority <= attack {
                     opponent.authority = 0;
                     self.state = State::WonBy(self.current_player)
                 } else {
                     opponent.authority -= attack;
                 }
                 player.attack = 0;
+                Ok(())
             }
-            NextTurn => self.next_turn(),
-            Activate(index) => {
-                // todo activate a card
-                let player = &mut self.players[self.current_player];
-                player.activate_played_card(index, &mut self.state);
+            NextTurn => {
+                self.next_turn();
+                Ok(())
             }
+            // todo activate a card
+            Activate(_) => Err(Rejected::Unsupported),
         }
     }
-    pub fn status_line(&self) -> impl Display {
-        StatusLine(self)
-    }
+}
+
+impl Game {
+    /// Undoes the last accepted [Event] of the current turn
+    ///
+    /// The turn is replayed from its start without this event,
+    /// the events of the previous turns cannot be undone.
+    pub fn undo(&mut self) -> Result<(), Rejected> {
+        let (start, played, logged) = self.turn_start.take().ok_or(Rejected::NothingToUndo)?;
+        if self.historic.len() == played {
+            self.turn_start = Some((start, played, logged));
+            return Err(Rejected::NothingToUndo);
+        }
 
-    // todo
-    fn scrap_card(&mut self, GamePosition { player, loc, index }: &GamePosition) {
-        self[*player][*loc].remove(*index);
+        let mut historic = std::mem::take(&mut self.historic);
+        let mut events = historic.split_off(played);
+        events.pop();
+        let mut log = std::mem::take(&mut self.log);
+        log.truncate(logged);
+        let mut game = Game {
+            seed: self.seed,
+            rules: (*start).clone(),
+            turn_start: None,
+            historic,
+            log,
+        };
+        for event in events {
+            let _ = game.apply_event(event);
+        }
+        game.turn_start = Some((start, played, logged));
+        *self = game;
+        Ok(())
     }
 }
 
-impl std::ops::Index<PlayerId> for Game {
-    type Output = Player;
-    fn index(&self, index: PlayerId) -> &Self::Output {
-        &self.players[index.to_usize()]
-    }
+/// Who looks at a [Game] played by others, see [Game::seen_by]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Viewer {
+    /// A player, the hand and the draw pile of its opponent are hidden
+    Seat(PlayerId),
+    /// A spectator of the live game, the hands and the draw piles are hidden
+    Spectator,
+    /// A spectator watching late, for example when casting a game,
+    /// the hands and the draw piles are revealed
+    Delayed,
 }
-impl std::ops::IndexMut<PlayerId> for Game {
-    fn index_mut(&mut self, index: PlayerId) -> &mut Self::Output {
-        &mut self.players[index.to_usize()]
+
+impl Game {
+    /// The [Game] as a [Viewer] may see it, without its seed nor its historic
+    ///
+    /// The trade deck is always hidden: the cards drawn from a hidden deck
+    /// cannot be known in advance by a [Viewer].
+    pub fn seen_by(&self, viewer: Viewer) -> Game {
+        let mut rules = self.rules.clone();
+        rules.rng = GameRng::restore(0, 0);
+        rules.deck.hide();
+        for player in [PlayerId::First, PlayerId::Second] {
+            let hidden = match viewer {
+                Viewer::Seat(seat) => seat != player,
+                Viewer::Spectator => true,
+                Viewer::Delayed => false,
+            };
+            if hidden {
+                rules[player].hand.hide();
+                rules[player].draw_pile.hide();
+            }
+        }
+        let mut game = Game {
+            seed: 0,
+            rules,
+            turn_start: None,
+            historic: vec![],
+            log: self.log.clone(),
+        };
+        game.save_turn_start();
+        game
     }
 }
 
-pub struct StatusLine<'a>(&'a Game);
-impl Display for StatusLine<'_> {
-    // todo: better adapt on the state of the game and precise
-    // position of cursor
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl Rules {
+    /// Returns the [Card] at a [GamePosition] if there is one
+    pub fn card_at(&self, position: &GamePosition) -> Option<&Card> {
+        self.zone(position.player, position.loc).get(position.index)
+    }
+    /// Returns where the [Card] with a given [CardId] is, if it is in a [Location]
+    ///
+    /// Remark: the cards of the shop are at the current player.
+    pub fn position_of(&self, id: CardId) -> Option<GamePosition> {
         use Location::*;
-        use State::*;
-        let Game {
-            players,
-            position,
-            state,
-            current_player,
-            selection,
-            ..
-        } = self.0;
-
-        if let State::WonBy(playerid) = state {
-            write!(f, "\t>>>>\t{} Player WON\t<<<<\t", playerid)?;
-            return Ok(());
+        let players = [self.current_player, self.current_player.other()];
+        [(self.current_player, Explorer), (self.current_player, Shop)]
+            .into_iter()
+            .chain(
+                players
+                    .into_iter()
+                    .flat_map(|player| [Hand, Played, Discard, DrawPile].map(|loc| (player, loc))),
+            )
+            .find_map(|(player, loc)| {
+                let index = self.zone(player, loc).position(id)?;
+                Some(GamePosition { player, loc, index })
+            })
+    }
+    /// Returns the [Deck] at a [Location] of a player
+    ///
+    /// Remark: the explorer and the shop are shared by both players.
+    pub fn zone(&self, player: PlayerId, loc: Location) -> &Deck {
+        match loc {
+            Location::Explorer => &self.explorer,
+            Location::Shop => &self.shop,
+            _ => &self[player][loc],
         }
+    }
+    /// Hash of the rules state of a [Game]
+    ///
+    /// The historic and the log are not part of the hash:
+    /// two mirrored games with different hashes have diverged.
+    pub fn state_hash(&self) -> u64 {
+        let mut hasher = StateHasher::default();
+        (
+            self.turn,
+            self.current_player,
+            self.next_id,
+            &self.state,
+            &self.players,
+            &self.deck,
+            &self.explorer,
+            &self.shop,
+            &self.scrap_heap,
+        )
+            .hash(&mut hasher);
+        hasher.finish()
+    }
+}
 
-        write!(f, "[->][↑][<-][↓] move")?;
-        write!(f, " | ")?;
-        write!(f, "[enter] ")?;
-        let width_enter = 20;
-        match state {
-            Playing => {
-                if players[*current_player].attack == 0 {
-                    write!(f, "{:^width$}", "Next Turn", width = width_enter)?;
-                } else {
-                    write!(f, "{:^width$}", "Attack Opponent", width = width_enter)?;
-                }
-            }
-            Scraping { nb, loc } => write!(
-                f,
-                "{:^width$}",
-                format!("Scrap {} cards selectioned", selection.len()),
-                width = width_enter,
-            )?,
-            Discarding { nb, loc } => write!(
-                f,
-                "{:^width$}",
-                format!("Discard {} cards selectioned", selection.len()),
-                width = width_enter
-            )?,
-            Copy => write!(
-                f,
-                "{:^width$}",
-                "Copy Ship selectioned",
-                width = width_enter
-            )?,
-            _ => (),
-        }
-        write!(f, " | ")?;
-        write!(f, "[space] ")?;
-
-        match state {
-            Playing => match position.loc {
-                Explorer => write!(f, "Buy an Explorer")?,
-                Shop => write!(f, "Buy a card from the shop")?,
-                Hand => write!(f, "Play a card from your hand")?,
-                Played => write!(f, "Activate this card")?,
-                Discard => write!(f, "")?,
-                DrawPile => write!(f, "")?,
-            },
-            Scraping { nb, loc } => write!(f, "Scrapping {} cards at {}", nb, loc)?,
-            Discarding { nb, loc } => write!(f, "Discarding {} cards at {}", nb, loc)?,
-            Copy => write!(f, "")?,
-            _ => (),
-        }
+/// FNV-1a hasher used by [Game::state_hash]
+///
+/// Remark: unlike [std::hash::DefaultHasher] it gives the same hash on every
+/// platform and with every version of Rust, integers are hashed as little endian.
+struct StateHasher(u64);
 
-        Ok(())
+impl Default for StateHasher {
+    fn default() -> Self {
+        Self(0xcbf29ce484222325)
     }
 }
 
-/* Global Layout of the Game
-
-----------------------------------------|-----------|
-| Played hand opponent Player | discard | draw_pile |
-|----------|------------------|---------|-----------|
-| Explorer |       Shop       |    info players     |
-|----------|------------------|---------------------|
-|  Played Hand current player |     discard         |
-|-----------------------------|---------------------|
-|    Hand current Player      |      draw pile      |
-|-----------------------------|---------------------|
-
-Rmk: the left colum is about 5 times bigger than the right column
-*/
-
-impl Widget for &Game {
-    fn render(self, area: Rect, buf: &mut Buffer)
-    where
-        Self: Sized,
-    {
-        use Constraint::*;
-        Block::bordered().render(area, buf);
-        let area = area.inner(Margin::new(1, 1));
-
-        // Layout the Grid
-        let horizontal = Layout::horizontal([Fill(5), Fill(1)]);
-        let vertical = Layout::vertical([Fill(1), Fill(1), Fill(1), Fill(1)]);
-        let status_line_layout = Layout::vertical([Fill(1), Length(1)]);
-        let shop_layout = Layout::horizontal([Fill(1), Fill(5)]);
-        let discard_draw_pile_layout = Layout::vertical([Fill(1), Fill(1)]);
-        let info_players_layout = Layout::vertical([Fill(1), Fill(1)]);
-
-        let layout = status_line_layout.split(area);
-        let (all_elements, status_line) = (layout[0], layout[1]);
-
-        let layout = horizontal.split(all_elements);
-        let (left, right) = (layout[0], layout[1]);
-
-        let layout = vertical.split(left);
-        let (played_op, explorer_shop, played_cur, hand_cur) =
-            (layout[0], layout[1], layout[2], layout[3]);
-
-        let layout = vertical.split(right);
-        let (discard_draw_pile_op, info_players, discard_cur, draw_pile_cur) =
-            (layout[0], layout[1], layout[2], layout[3]);
-
-        let layout = shop_layout.split(explorer_shop);
-        let (explorer, shop) = (layout[0], layout[1]);
-
-        let layout = discard_draw_pile_layout.split(discard_draw_pile_op);
-        let (draw_pile_op, discard_op) = (layout[0], layout[1]);
-
-        let layout = info_players_layout.split(info_players);
-        let info_players = [layout[1], layout[0]];
-
-        let played_players = [played_cur, played_op];
-        let hand_players = [hand_cur, Rect::new(0, 0, 0, 0)];
-        let discard_players = [discard_cur, discard_op];
-        let draw_pile_players = [draw_pile_cur, draw_pile_op];
-        // Layout the Grid
-
-        // Shop
-        self.explorer
-            .widget()
-            .set_name("Explorer")
-            .set_selection(&self, Location::Explorer, PlayerId::First)
-            .render(explorer, buf);
-        self.shop
-            .widget()
-            .set_name("Shop")
-            .set_selection(&self, Location::Shop, PlayerId::First)
-            .render(shop, buf);
-
-        // The two players
-        for id in 0..2 {
-            // 0 is at the bottom is the current player
-            // 1 is at the top
-            let played = played_players[id];
-            let hand = hand_players[id];
-            let discard = discard_players[id];
-            let draw_pile = draw_pile_players[id];
-            let info = info_players[id];
-            let id = if id == 0 {
-                self.current_player
-            } else {
-                self.current_player.other()
-            };
-            // the current player
-            let player = &self.players[id];
-
-            // Played Hand of the player
-            player
-                .played
-                .widget()
-                .set_name(&format!("Played {} player", id))
-                .set_selection(self, Location::Played, id)
-                .render(played, buf);
-            // Hand of the player
-            player
-                .hand
-                .widget()
-                .set_name(&format!("Hand {} player", id))
-                .set_selection(self, Location::Hand, id)
-                .render(hand, buf);
-
-            // Draw info about current player
-            Paragraph::new(format!("{}", player))
-                .centered()
-                .render(info, buf);
-            // Draw pile of the current player
-            if player
-                .draw_pile
-                .widget()
-                .set_name(&format!("Draw Pile {} player", id))
-                .set_max_cols(6)
-                .set_selection(self, Location::DrawPile, id)
-                .hidden()
-                .render(draw_pile, buf)
-            {
-                return;
-            }
-            // Discard of the current player
-            if player
-                .discard
-                .widget()
-                .set_name(&format!("Discard {} player", id))
-                .set_max_cols(6)
-                .set_selection(self, Location::Discard, id)
-                .hidden()
-                .render(discard, buf)
-            {
-                return;
-            }
+impl Hasher for StateHasher {
+    fn finish(&self) -> u64 {
+        self.0
+    }
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 ^= *byte as u64;
+            self.0 = self.0.wrapping_mul(0x100000001b3);
         }
+    }
+    fn write_u16(&mut self, i: u16) {
+        self.write(&i.to_le_bytes())
+    }
+    fn write_u32(&mut self, i: u32) {
+        self.write(&i.to_le_bytes())
+    }
+    fn write_u64(&mut self, i: u64) {
+        self.write(&i.to_le_bytes())
+    }
+    fn write_usize(&mut self, i: usize) {
+        self.write_u64(i as u64)
+    }
+    fn write_isize(&mut self, i: isize) {
+        self.write_u64(i as u64)
+    }
+}
 
-        // Status Line
-        Paragraph::new(format!("{}", self.status_line())).render(status_line, buf);
+impl std::ops::Index<PlayerId> for Rules {
+    type Output = Player;
+    fn index(&self, index: PlayerId) -> &Self::Output {
+        &self.players[index.to_usize()]
+    }
+}
+impl std::ops::IndexMut<PlayerId> for Rules {
+    fn index_mut(&mut self, index: PlayerId) -> &mut Self::Output {
+        &mut self.players[index.to_usize()]
     }
 }
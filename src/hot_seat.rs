@@ -0,0 +1,46 @@
+use crate::{
+    game::Game,
+    input::Input,
+    player_id::PlayerId,
+    state::State,
+    tui::view::{Gesture, View},
+};
+
+/// Two players sharing the same terminal
+///
+/// Between two turns the hands are hidden until the next player
+/// confirms they have the keyboard.
+#[derive(Debug)]
+pub struct HotSeat {
+    pub game: Game,
+    /// What the players see, they share the cursor
+    pub view: View,
+    /// The player that has to confirm before seeing its hand
+    pub waiting: Option<PlayerId>,
+}
+
+impl HotSeat {
+    pub fn new(game: Game) -> Self {
+        Self {
+            waiting: Some(game.current_player),
+            game,
+            view: View::default(),
+        }
+    }
+    pub fn interact(&mut self, input: Input) {
+        self.gesture(Gesture::Input(input));
+    }
+    pub fn gesture(&mut self, gesture: Gesture) {
+        if self.waiting.is_some() {
+            if let Gesture::Input(Input::Enter) = gesture {
+                self.waiting = None;
+            }
+            return;
+        }
+        let playing = self.game.current_player;
+        self.view.gesture(&mut self.game, gesture);
+        if self.game.current_player != playing && !matches!(self.game.state, State::WonBy(_)) {
+            self.waiting = Some(self.game.current_player);
+        }
+    }
+}
@@ -1,5 +1,10 @@
-use ratatui::crossterm::event::Event;
+#[cfg(feature = "tui")]
+use crate::bindings::Bindings;
+use rand::{Rng, RngExt};
+#[cfg(feature = "tui")]
+use ratatui::crossterm::event::{Event, MouseEvent, MouseEventKind};
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Input {
     Enter,
     Space,
@@ -7,36 +12,157 @@ pub enum Input {
     Right,
     Up,
     Down,
+    /// Undo the last action of the turn
+    Undo,
+    /// Show or hide the key bindings
+    Help,
+    /// End the turn without attacking
+    EndTurn,
+    /// Play every card of the hand
+    PlayAll,
+    /// Clear the selected cards
+    Cancel,
+    /// Show or hide the details of the card under the cursor
+    Zoom,
+    /// Suggest the next move with its reason
+    Hint,
+    /// Show or hide the log of the game
+    Log,
+    Quit,
+    ScrollUp,
+    ScrollDown,
     Other,
 }
+
+/// Inputs that can be bound to keys, in the order of the help screen
+pub const KEY_INPUTS: [Input; 17] = [
+    Input::Enter,
+    Input::Space,
+    Input::Left,
+    Input::Right,
+    Input::Up,
+    Input::Down,
+    Input::Undo,
+    Input::EndTurn,
+    Input::PlayAll,
+    Input::Cancel,
+    Input::Zoom,
+    Input::Hint,
+    Input::Log,
+    Input::ScrollUp,
+    Input::ScrollDown,
+    Input::Help,
+    Input::Quit,
+];
+
+/// Inputs read from the terminal
+#[cfg(feature = "tui")]
 impl Input {
+    /// Input from a terminal [Event] with the default [Bindings]
     pub fn from(event: Event) -> Self {
+        Bindings::default().input(&event)
+    }
+    /// Input from the wheel of the mouse,
+    /// the clicks are [Mouse](crate::tui::view::Mouse) moves
+    pub fn from_mouse(mouse_event: MouseEvent) -> Self {
+        use Input::*;
+        match mouse_event.kind {
+            MouseEventKind::ScrollUp => ScrollUp,
+            MouseEventKind::ScrollDown => ScrollDown,
+            _ => Other,
+        }
+    }
+}
+
+impl Input {
+    /// Name of an input in the key bindings file
+    pub fn name(&self) -> &'static str {
         use Input::*;
-        if let Event::Key(key_event) = event {
-            use ratatui::crossterm::event::KeyCode;
-            match key_event.code {
-                KeyCode::Char(' ') => Space,
-                KeyCode::Enter => Enter,
-                KeyCode::Left | KeyCode::Char('h') => Left,
-                KeyCode::Right | KeyCode::Char('l') => Right,
-                KeyCode::Up | KeyCode::Char('k') => Up,
-                KeyCode::Down | KeyCode::Char('j') => Down,
-                _ => Other,
-            }
-        } else {
-            Other
+        match self {
+            Enter => "confirm",
+            Space => "select",
+            Left => "left",
+            Right => "right",
+            Up => "up",
+            Down => "down",
+            Undo => "undo",
+            Help => "help",
+            EndTurn => "end-turn",
+            PlayAll => "play-all",
+            Cancel => "cancel",
+            Zoom => "zoom",
+            Hint => "hint",
+            Log => "log",
+            Quit => "quit",
+            ScrollUp => "scroll-up",
+            ScrollDown => "scroll-down",
+            Other => "other",
         }
     }
-    pub fn random() -> Self {
+    /// What an input does, shown on the help screen
+    pub fn description(&self) -> &'static str {
         use Input::*;
-        match rand::random_range(0..6) {
+        match self {
+            Enter => "attack, end the turn or confirm the chosen cards",
+            Space => "play, buy, activate or choose the card under the cursor",
+            Left => "move the cursor left",
+            Right => "move the cursor right",
+            Up => "move the cursor up",
+            Down => "move the cursor down",
+            Undo => "undo the last action of the turn",
+            Help => "show or hide the key bindings",
+            EndTurn => "end the turn without attacking",
+            PlayAll => "play every card of the hand",
+            Cancel => "clear the chosen cards",
+            Zoom => "show or hide the details of the card",
+            Hint => "suggest the next move and explain it",
+            Log => "show or hide the log of the game",
+            Quit => "quit the game",
+            ScrollUp => "scroll up a popup or the log",
+            ScrollDown => "scroll down a popup or the log",
+            Other => "nothing",
+        }
+    }
+    pub fn random<R: Rng>(rng: &mut R) -> Self {
+        use Input::*;
+        match rng.random_range(0..16) {
             0 => Enter,
             1 => Space,
             2 => Left,
             3 => Right,
             4 => Up,
             5 => Down,
+            6 => ScrollUp,
+            7 => ScrollDown,
+            8 => Undo,
+            9 => EndTurn,
+            10 => PlayAll,
+            11 => Cancel,
+            12 => Zoom,
+            13 => Help,
+            14 => Hint,
+            15 => Log,
             _ => unreachable!(),
         }
     }
 }
+
+/// Writes an [Input] with its name, for example `confirm`
+impl std::fmt::Display for Input {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+impl std::str::FromStr for Input {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let name = s.trim();
+        KEY_INPUTS
+            .into_iter()
+            .chain([Input::Other])
+            .find(|input| input.name() == name)
+            .ok_or_else(|| format!("unknown input '{s}'"))
+    }
+}
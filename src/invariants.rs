@@ -0,0 +1,111 @@
+//! Rules a [Game] follows after every [Event](crate::event::Event),
+//! checked by the fuzzers
+
+use crate::{
+    card::{Card, CardId},
+    deck::Deck,
+    game::Game,
+    player_id::PlayerId,
+    state::State,
+};
+use std::sync::LazyLock;
+
+/// Larger gold, attack or authority than any game gives, it comes from an underflow
+pub const MAX_RESOURCE: u32 = 1 << 24;
+
+/// Number of cards in a game, the explorers put aside
+static NB_CARDS: LazyLock<usize> =
+    LazyLock::new(|| Deck::starter_complete_deck().len() + 2 * Deck::starter_deck_player().len());
+
+/// Checks every invariant of a [Game], returns the first one broken
+pub fn check(game: &Game) -> Result<(), String> {
+    check_cards(game)?;
+    check_ids(game)?;
+    check_resources(game)
+}
+
+/// Cards are neither created nor lost: they move between the decks of the players,
+/// the shop, the trade deck and the scrap heap
+///
+/// Remark: the explorers are an endless supply, they are not counted.
+pub fn check_cards(game: &Game) -> Result<(), String> {
+    let explorer = Card::explorer().name();
+    let is_explorer = |card: &Card| card.name() == explorer;
+    if game.explorer.len() != 1 || !game.explorer.iter().all(is_explorer) {
+        return Err(String::from("the explorer pile has changed"));
+    }
+    let decks = [&game.deck, &game.shop, &game.scrap_heap]
+        .into_iter()
+        .chain(game.players.iter().flat_map(|player| {
+            [
+                &player.hand,
+                &player.played,
+                &player.discard,
+                &player.draw_pile,
+            ]
+        }));
+    let nb_cards = decks
+        .flat_map(Deck::iter)
+        .filter(|card| !is_explorer(card))
+        .count();
+    if nb_cards != *NB_CARDS {
+        return Err(format!(
+            "{nb_cards} cards in the game instead of {}",
+            *NB_CARDS
+        ));
+    }
+    Ok(())
+}
+
+/// Two cards of a [Game] never have the same [CardId]
+pub fn check_ids(game: &Game) -> Result<(), String> {
+    let decks = [&game.deck, &game.explorer, &game.shop, &game.scrap_heap]
+        .into_iter()
+        .chain(game.players.iter().flat_map(|player| {
+            [
+                &player.hand,
+                &player.played,
+                &player.discard,
+                &player.draw_pile,
+            ]
+        }));
+    // The ids are given in order from 0, they are small
+    let mut seen = vec![false; 2 * *NB_CARDS];
+    for CardId(id) in decks.flat_map(Deck::iter).map(|card| card.id) {
+        let id = id as usize;
+        if id >= seen.len() {
+            seen.resize(2 * id, false);
+        }
+        if std::mem::replace(&mut seen[id], true) {
+            return Err(format!("two cards have the id {}", CardId(id as u32)));
+        }
+    }
+    Ok(())
+}
+
+/// The gold, attack and authority never underflow,
+/// the authority of a player is 0 only when the game is won by its opponent
+pub fn check_resources(game: &Game) -> Result<(), String> {
+    for id in [PlayerId::First, PlayerId::Second] {
+        let player = &game.players[id];
+        for (name, value) in [
+            ("gold", player.gold),
+            ("attack", player.attack),
+            ("authority", player.authority),
+        ] {
+            if value >= MAX_RESOURCE {
+                return Err(format!(
+                    "the {name} of the {id} player underflowed: {value}"
+                ));
+            }
+        }
+        let lost = matches!(game.state, State::WonBy(winner) if winner != id);
+        if (player.authority == 0) != lost {
+            return Err(format!(
+                "the {id} player has {} authority in the state {:?}",
+                player.authority, game.state
+            ));
+        }
+    }
+    Ok(())
+}
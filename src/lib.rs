@@ -1,15 +1,35 @@
 pub mod action;
+pub mod ai;
+#[cfg(feature = "tui")]
+pub mod app;
+#[cfg(feature = "tui")]
+pub mod bindings;
+pub mod bot;
 
 pub mod card;
+pub mod command;
 pub mod deck;
 pub mod event;
 pub mod faction;
+pub mod fuzz;
 pub mod game;
+#[cfg(feature = "tui")]
+pub mod hot_seat;
 pub mod input;
+pub mod invariants;
+pub mod log;
 pub mod player;
 pub mod player_id;
+pub mod position;
+pub mod record;
+pub mod rl;
+pub mod selfplay;
 pub mod state;
+pub mod theme;
+#[cfg(feature = "tui")]
+pub mod tui;
 
 pub mod network;
+pub mod notation;
 
 pub mod selection;
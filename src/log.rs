@@ -0,0 +1,65 @@
+//! Readable log of the accepted [Event](crate::event::Event)s of a [Game](crate::game::Game)
+//!
+//! Like the historic, only the accepted events are logged,
+//! with their cards named: `Turn 4 — 2nd bought Cutter for 2🪙`.
+
+use crate::{action::Action, player_id::PlayerId};
+use std::fmt::Display;
+
+/// What a player did, an event playing several cards gives one entry per card
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Logged {
+    Bought {
+        card: &'static str,
+        gold: u32,
+    },
+    Played(&'static str),
+    Scrapped(&'static str),
+    Discarded(&'static str),
+    Copied(&'static str),
+    /// A choice of no card
+    ChoseNothing,
+    /// An attack with the authority left to the opponent
+    Attacked {
+        attack: u32,
+        authority: u32,
+    },
+    Won,
+    EndedTurn,
+}
+
+/// An entry of the log of a [Game](crate::game::Game)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LogEntry {
+    pub turn: u32,
+    pub player: PlayerId,
+    pub logged: Logged,
+}
+
+impl Display for Logged {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        use Logged::*;
+        match self {
+            Bought { card, gold } => write!(f, "bought {card} for {}", Action::Gold(*gold)),
+            Played(card) => write!(f, "played {card}"),
+            Scrapped(card) => write!(f, "scrapped {card}"),
+            Discarded(card) => write!(f, "discarded {card}"),
+            Copied(card) => write!(f, "copied {card}"),
+            ChoseNothing => write!(f, "chose no card"),
+            Attacked { attack, authority } => write!(
+                f,
+                "attacked for {}, {} left",
+                Action::Attack(*attack),
+                Action::Authority(*authority)
+            ),
+            Won => write!(f, "won the game"),
+            EndedTurn => write!(f, "ended the turn"),
+        }
+    }
+}
+
+impl Display for LogEntry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Turn {} — {} {}", self.turn, self.player, self.logged)
+    }
+}
@@ -1,29 +1,390 @@
-use ratatui::crossterm::event::{Event, KeyCode, KeyEvent, poll, read};
+use ratatui::crossterm::event::{DisableMouseCapture, EnableMouseCapture, Event, poll, read};
+use ratatui::crossterm::execute;
+use ratatui::layout::Rect;
+use ratatui::style::Stylize;
+use ratatui::text::Line;
 use ratatui::widgets::Widget;
+use stars_gapa::app::{App, Exit, Mode};
+use stars_gapa::bindings::Bindings;
+use stars_gapa::bot::{self, Bot, Seat};
+use stars_gapa::command;
 use stars_gapa::game::Game;
 use stars_gapa::input::Input;
+use stars_gapa::network::client::{Client, Mirror};
+use stars_gapa::network::message::{ClientMessage, ServerMessage};
+use stars_gapa::network::server::Server;
+use stars_gapa::player_id::PlayerId;
+use stars_gapa::selfplay::{self, Agent};
+use stars_gapa::theme::{Palette, Theme};
+use stars_gapa::tui::view::{Mouse, View};
+use std::path::PathBuf;
+use std::sync::mpsc::{Receiver, TryRecvError};
 use std::time::Duration;
 
+const DEFAULT_ADDR: &str = "127.0.0.1:7878";
+
+/// Path of the key bindings file,
+/// `$STARS_GAPA_KEYS` or else `~/.config/stars-gapa/keys`
+fn bindings_path() -> Option<PathBuf> {
+    match std::env::var_os("STARS_GAPA_KEYS") {
+        Some(path) => Some(PathBuf::from(path)),
+        None => {
+            std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config/stars-gapa/keys"))
+        }
+    }
+}
+
+fn load_bindings() -> Result<Bindings, Box<dyn std::error::Error>> {
+    match bindings_path() {
+        Some(path) => Ok(Bindings::load(path)?),
+        None => Ok(Bindings::default()),
+    }
+}
+
+/// Runs the terminal application with the mouse events captured
+fn run<T>(
+    app: impl FnOnce(&mut ratatui::DefaultTerminal) -> Result<T, Box<dyn std::error::Error>>,
+) -> Result<T, Box<dyn std::error::Error>> {
+    execute!(std::io::stdout(), EnableMouseCapture)?;
+    let result = ratatui::run(app);
+    execute!(std::io::stdout(), DisableMouseCapture)?;
+    result
+}
+
 fn main() -> Result<(), Box<dyn std::error::Error>> {
-    let mut game = Game::new();
+    // STARS_GAPA_THEME=ascii when the emoji are not displayed well
+    if let Some(theme) = std::env::var_os("STARS_GAPA_THEME") {
+        theme.to_string_lossy().parse::<Theme>()?.set();
+    }
+    // STARS_GAPA_PALETTE=colour-blind for colours told apart by everyone
+    if let Some(palette) = std::env::var_os("STARS_GAPA_PALETTE") {
+        palette.to_string_lossy().parse::<Palette>()?.set();
+    }
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    match args.first().map(String::as_str) {
+        // stars-gapa serve [addr]
+        Some("serve") => {
+            let server = Server::bind(args.get(1).map_or(DEFAULT_ADDR, String::as_str))?;
+            println!("Lobby listening on {}", server.local_addr()?);
+            server.run()?;
+            Ok(())
+        }
+        // stars-gapa join [addr] [table name]
+        Some("join") => play_online(
+            args.get(1).map_or(DEFAULT_ADDR, String::as_str),
+            args.get(2).map_or("table", String::as_str),
+        ),
+        // stars-gapa rejoin [addr] <token>
+        Some("rejoin") => rejoin_online(
+            args.get(1).map_or(DEFAULT_ADDR, String::as_str),
+            args.get(2)
+                .ok_or("missing the token of the seat")?
+                .parse()?,
+        ),
+        // stars-gapa watch [addr] [table number] [delay in seconds]
+        Some("watch") => watch_online(
+            args.get(1).map_or(DEFAULT_ADDR, String::as_str),
+            args.get(2).map_or(Ok(0), |table| table.parse())?,
+            args.get(3).map_or(Ok(0), |delay| delay.parse())?,
+        ),
+        // stars-gapa script [seed]
+        // plays the commands read on the standard input, see `help`
+        Some("script") => {
+            let mut game = match args.get(1) {
+                Some(seed) => Game::with_seed(seed.parse()?),
+                None => Game::new(),
+            };
+            let failures = command::run(&mut game, std::io::stdin().lock(), std::io::stdout())?;
+            match failures {
+                0 => Ok(()),
+                _ => Err(format!("{failures} commands failed").into()),
+            }
+        }
+        // stars-gapa match <bot> <bot> [seed]
+        // plays two bots, `ai` or a command speaking the protocol of the bots
+        Some("match") => {
+            let mut game = match args.get(3) {
+                Some(seed) => Game::with_seed(seed.parse()?),
+                None => Game::new(),
+            };
+            // STARS_GAPA_BOT_TIMEOUT=n gives n milliseconds to the bots to answer
+            let timeout = match std::env::var("STARS_GAPA_BOT_TIMEOUT") {
+                Ok(millis) => Duration::from_millis(millis.parse()?),
+                Err(_) => bot::DEFAULT_TIMEOUT,
+            };
+            let seat = |i: usize, id: PlayerId| match args.get(i).map(String::as_str) {
+                None => Err(String::from("missing the command of a bot")),
+                Some("ai") => Ok(Seat::Computer),
+                Some(command) => Bot::spawn(command, id, timeout)
+                    .map(Seat::Bot)
+                    .map_err(|error| format!("{command}: {error}")),
+            };
+            let seats = [seat(1, PlayerId::First)?, seat(2, PlayerId::Second)?];
+            let outcome = bot::play_match(&mut game, seats, 1000);
+            print!("{}", game.record());
+            println!("# {outcome}");
+            Ok(())
+        }
+        // stars-gapa selfplay <games> [first seed] [threads] [agent] [agent]
+        // writes the decisions of games between `greedy` or `random` agents as JSON lines
+        Some("selfplay") => {
+            let nb_games = args.get(1).ok_or("missing the number of games")?.parse()?;
+            let first_seed = args.get(2).map_or(Ok(0), |seed| seed.parse())?;
+            let nb_threads = match args.get(3) {
+                Some(threads) => threads.parse()?,
+                None => std::thread::available_parallelism().map_or(1, |nb| nb.get()),
+            };
+            let agent = |i: usize| args.get(i).map_or(Ok(Agent::Greedy), |agent| agent.parse());
+            let agents = [agent(4)?, agent(5)?];
+            let output = std::io::BufWriter::new(std::io::stdout().lock());
+            selfplay::generate(first_seed, nb_games, nb_threads, agents, 1000, output)?;
+            Ok(())
+        }
+        // stars-gapa keys
+        // prints the current key bindings in the format of the bindings file
+        Some("keys") => {
+            if let Some(path) = bindings_path() {
+                println!("# {}", path.display());
+            }
+            print!("{}", load_bindings()?);
+            Ok(())
+        }
+        // stars-gapa hotseat
+        Some("hotseat") => {
+            let mut app = App::new(load_bindings()?, data_dir());
+            app.start(Mode::HotSeat);
+            play_app(app)
+        }
+        _ => play_app(App::new(load_bindings()?, data_dir())),
+    }
+}
+
+/// Directory of the saved games and of the replays,
+/// `$STARS_GAPA_DATA` or else `~/.local/share/stars-gapa`
+fn data_dir() -> PathBuf {
+    match std::env::var_os("STARS_GAPA_DATA") {
+        Some(path) => PathBuf::from(path),
+        None => std::env::var_os("HOME").map_or(PathBuf::from("."), |home| {
+            PathBuf::from(home).join(".local/share/stars-gapa")
+        }),
+    }
+}
+
+/// Runs the menus and the games of the [App],
+/// the network games are played once the [App] is left
+fn play_app(mut app: App) -> Result<(), Box<dyn std::error::Error>> {
+    loop {
+        let exit = run(|terminal| {
+            loop {
+                terminal.draw(|frame| (&app).render(frame.area(), frame.buffer_mut()))?;
+                if poll(Duration::from_millis(100))? {
+                    app.handle_event(&read()?);
+                }
+                // Even while the mouse moves, the computer keeps playing
+                app.tick();
+                if let Some(exit) = app.take_exit() {
+                    return Ok(exit);
+                }
+            }
+        })?;
+        let result =
+            match exit {
+                Exit::Quit => return Ok(()),
+                Exit::Host { addr, table } => Server::bind(addr.as_str())
+                    .map_err(Into::into)
+                    .and_then(|server| {
+                        server.spawn();
+                        play_online(&addr, &table)
+                    }),
+                Exit::Join { addr, table } => play_online(&addr, &table),
+            };
+        if let Err(error) = result {
+            app.message(error.to_string());
+        }
+    }
+}
+
+/// Sits at the first free seat of the table called `name`,
+/// the table is created if there is none.
+fn find_seat(client: &mut Client, name: &str) -> Result<(), Box<dyn std::error::Error>> {
+    client.send(&ClientMessage::List)?;
+    let ServerMessage::Tables(tables) = client.recv()? else {
+        return Err("the server did not list its tables".into());
+    };
+    let free_seat = tables
+        .iter()
+        .filter(|table| table.name == name)
+        .find_map(|table| table.free.first().map(|&seat| (table.id, seat)));
+    let (table, seat) = match free_seat {
+        Some(free_seat) => free_seat,
+        None => {
+            client.send(&ClientMessage::Create(name.into()))?;
+            let ServerMessage::Created(table) = client.recv()? else {
+                return Err("the server did not create the table".into());
+            };
+            (table, PlayerId::First)
+        }
+    };
+    client.send(&ClientMessage::Join { table, seat })?;
+    Ok(())
+}
+
+fn play_online(addr: &str, name: &str) -> Result<(), Box<dyn std::error::Error>> {
+    let mut client = Client::connect(addr)?;
+    find_seat(&mut client, name)?;
+    let mut mirror = Mirror::default();
+    wait_start(&client, &mut mirror)?;
+    mirror_game(addr, client, mirror)
+}
+
+/// Sits back at a table after a disconnection
+fn rejoin_online(addr: &str, token: u64) -> Result<(), Box<dyn std::error::Error>> {
+    let mut client = Client::connect(addr)?;
+    client.send(&ClientMessage::Reconnect { token })?;
+    let mut mirror = Mirror::default();
+    wait_start(&client, &mut mirror)?;
+    mirror_game(addr, client, mirror)
+}
 
-    ratatui::run(|terminal| {
-        terminal.draw(|frame| (&game).render(frame.area(), frame.buffer_mut()))?;
+/// Watches a table, the server reveals the hands only when the game is delayed
+fn watch_online(addr: &str, table: usize, delay: u64) -> Result<(), Box<dyn std::error::Error>> {
+    let mut client = Client::connect(addr)?;
+    client.send(&ClientMessage::Spectate { table, delay })?;
+    let mut mirror = Mirror::default();
+    wait_start(&client, &mut mirror)?;
+    mirror_game(addr, client, mirror)
+}
+
+/// Waits for the game of the table to start
+fn wait_start(client: &Client, mirror: &mut Mirror) -> Result<(), Box<dyn std::error::Error>> {
+    while mirror.game.is_none() {
+        let message = client.recv()?;
+        match &message {
+            ServerMessage::Joined { table, seat, token } => println!(
+                "Seated as {seat} player at table {table}, rejoin it with the token {token}"
+            ),
+            ServerMessage::Watching { table, .. } => println!("Watching table {table}"),
+            ServerMessage::Error(error) => return Err(error.clone().into()),
+            ServerMessage::Closed => return Err("the table has been closed".into()),
+            _ => (),
+        }
+        mirror.update(message);
+    }
+    Ok(())
+}
+
+/// Displays a [Game] played on a server,
+/// the cards the client may not see are sent face down
+///
+/// A player is reconnected to its seat in the background when the connection
+/// is lost, the game is still displayed meanwhile.
+fn mirror_game(
+    addr: &str,
+    mut client: Client,
+    mut mirror: Mirror,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let bindings = load_bindings()?;
+    let mut help = false;
+    let mut view = View::default();
+    let seat = mirror.seat;
+    let draw = |terminal: &mut ratatui::DefaultTerminal,
+                game: &Game,
+                view: &View,
+                help: bool,
+                reconnecting: bool| {
+        let widget = game.widget(view);
+        terminal
+            .draw(|frame| {
+                let area = frame.area();
+                widget.render(area, frame.buffer_mut());
+                if reconnecting {
+                    Line::from(" Connection lost, reconnecting… ")
+                        .centered()
+                        .yellow()
+                        .render(Rect { height: 1, ..area }, frame.buffer_mut());
+                }
+                if help {
+                    bindings.render(frame.area(), frame.buffer_mut());
+                }
+            })
+            .map(|_| ())
+    };
+    run(|terminal| {
+        let mut redraw = true;
+        let mut reconnecting: Option<Receiver<Result<Client, String>>> = None;
         loop {
-            if poll(Duration::from_millis(100))? {
-                let event = read()?;
-                if let Event::Key(KeyEvent {
-                    code: KeyCode::Esc | KeyCode::Char('q'),
-                    ..
-                }) = event
+            let mut changed = false;
+            let reconnected = reconnecting
+                .as_ref()
+                .and_then(|receiver| receiver.try_recv().ok());
+            if let Some(reconnected) = reconnected {
+                // The seat is lost when the server refuses the token
+                client = reconnected?;
+                reconnecting = None;
+                redraw = true;
+            }
+            while reconnecting.is_none() {
+                match client.try_recv() {
+                    Ok(message) => {
+                        if let Some(answer) = mirror.update(message) {
+                            // A lost connection is noticed by the reading thread
+                            let _ = client.send(&answer);
+                        }
+                        changed = true;
+                    }
+                    Err(TryRecvError::Empty) => break,
+                    Err(TryRecvError::Disconnected) => {
+                        let Some(token) = mirror.token else {
+                            return Ok(());
+                        };
+                        reconnecting = Some(Client::reconnect(addr, token));
+                        redraw = true;
+                    }
+                }
+            }
+            if mirror.closed {
+                break;
+            }
+            let Some(game) = &mut mirror.game else {
+                break;
+            };
+            if changed {
+                view.refresh(game);
+                redraw = true;
+            }
+            if poll(Duration::from_millis(50))? {
+                // The game only changes with the positions sent by the server,
+                // it cannot be undone
+                let event = match read()? {
+                    Event::Mouse(mouse_event) if let Some(mouse) = Mouse::from(mouse_event) => {
+                        view.get_mouse_event(game, mouse)
+                    }
+                    event => match bindings.input(&event) {
+                        Input::Quit => break,
+                        Input::Help => {
+                            help = !help;
+                            None
+                        }
+                        // Only the seat playing gets hints, they would tell the other hand
+                        Input::Hint if seat != Some(game.current_player) => None,
+                        input => view.get_event(game, input),
+                    },
+                };
+                // The events played while reconnecting are lost
+                if let Some(seat) = seat
+                    && let Some(event) = event
+                    && game.current_player == seat
+                    && reconnecting.is_none()
                 {
-                    break;
+                    let _ = client.send(&ClientMessage::Play(event));
                 }
-                game.interact(Input::from(event));
-                terminal.draw(|frame| (&game).render(frame.area(), frame.buffer_mut()))?;
+                redraw = true;
+            }
+            if redraw {
+                draw(terminal, game, &view, help, reconnecting.is_some())?;
+                redraw = false;
             }
         }
         Ok(())
     })
 }
-
@@ -1,21 +1,24 @@
-use std::io::Read;
+//! Play a [Game](crate::game::Game) over the network
+//!
+//! A [server::Server] hosts a lobby of tables, each table runs its own
+//! [Game](crate::game::Game) in its own thread. Clients list the open tables,
+//! create or join one and pick a seat. A table nobody joins is closed after
+//! a [while](server::ABANDONED_AFTER).
+//!
+//! The protocol is line based, each line is a [message::ClientMessage]
+//! or a [message::ServerMessage]. Only the table holds the seed and the
+//! random number generator of its game. Once both seats are taken, and after
+//! every accepted [Event](crate::event::Event), each client receives the
+//! [position](crate::position) of the game as it may
+//! [see](crate::game::Game::seen_by) it: the cards drawn by a player
+//! reach its seat only, the opponent sees their backs.
+//!
+//! A player losing its connection keeps its seat and comes back with the
+//! token it received when joining, a started game waits for its players
+//! a [while](server::ABANDONED_AFTER) once they are all gone.
+//! Every position comes with its [state hash](crate::game::Rules::state_hash)
+//! as a checksum, a client reading a garbled position asks for it again.
 
-pub fn do_connection() {
-    let addr = "127.0.0.8";
-    match std::net::TcpListener::bind(addr) {
-        Ok(tcp_listener) => (),
-        Err(_) => (),
-    }
-
-    let mut tcp_stream = match std::net::TcpStream::connect(addr) {
-        Ok(tcp_stream) => tcp_stream,
-        Err(_) => panic!(),
-    };
-
-    let mut buf: [u8; _] = [0; 1000];
-
-    match tcp_stream.read(&mut buf) {
-        Ok(i) => todo!(),
-        Err(_) => todo!(),
-    }
-}
+pub mod client;
+pub mod message;
+pub mod server;
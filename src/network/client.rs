@@ -0,0 +1,151 @@
+use crate::{
+    game::Game,
+    network::message::{ClientMessage, ServerMessage},
+    player_id::PlayerId,
+};
+use std::{
+    io::{self, BufRead, BufReader, Write},
+    net::{Shutdown, TcpStream, ToSocketAddrs},
+    sync::mpsc::{Receiver, RecvError, RecvTimeoutError, TryRecvError, channel},
+    thread,
+    time::Duration,
+};
+
+/// Time the server has to answer a client sitting back
+const RECONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Connection of a client to a [Server](crate::network::server::Server)
+///
+/// The messages of the server are read in a background thread
+/// so that they can be polled without blocking.
+pub struct Client {
+    writer: TcpStream,
+    incoming: Receiver<ServerMessage>,
+}
+
+impl Client {
+    pub fn connect<A: ToSocketAddrs>(addr: A) -> io::Result<Self> {
+        let writer = TcpStream::connect(addr)?;
+        let reader = BufReader::new(writer.try_clone()?);
+        let (sender, incoming) = channel();
+        thread::spawn(move || {
+            for line in reader.lines() {
+                let Ok(line) = line else {
+                    break;
+                };
+                let message = line.parse().unwrap_or_else(ServerMessage::Error);
+                if sender.send(message).is_err() {
+                    break;
+                }
+            }
+        });
+        Ok(Self { writer, incoming })
+    }
+    pub fn send(&mut self, message: &ClientMessage) -> io::Result<()> {
+        writeln!(self.writer, "{message}")
+    }
+    /// Waits for the next message of the server
+    pub fn recv(&self) -> Result<ServerMessage, RecvError> {
+        self.incoming.recv()
+    }
+    /// Waits at most `timeout` for the next message of the server
+    pub fn recv_timeout(&self, timeout: Duration) -> Result<ServerMessage, RecvTimeoutError> {
+        self.incoming.recv_timeout(timeout)
+    }
+    /// Returns the next message of the server if one has already arrived
+    pub fn try_recv(&self) -> Result<ServerMessage, TryRecvError> {
+        self.incoming.try_recv()
+    }
+    /// Sits back on the seat of a `token` in a background thread,
+    /// trying again every second until the server answers
+    ///
+    /// The [Client] is received once sat back, or the error of the server
+    /// refusing the token. The user interface keeps running meanwhile.
+    pub fn reconnect(addr: &str, token: u64) -> Receiver<Result<Client, String>> {
+        let addr = String::from(addr);
+        let (sender, receiver) = channel();
+        thread::spawn(move || {
+            loop {
+                thread::sleep(Duration::from_secs(1));
+                let Ok(mut client) = Client::connect(&addr) else {
+                    continue;
+                };
+                if client.send(&ClientMessage::Reconnect { token }).is_err() {
+                    continue;
+                }
+                let answer = match client.recv_timeout(RECONNECT_TIMEOUT) {
+                    Ok(ServerMessage::Joined { .. }) => Ok(client),
+                    Ok(ServerMessage::Error(error)) => Err(error),
+                    // The connection is lost again
+                    _ => continue,
+                };
+                let _ = sender.send(answer);
+                return;
+            }
+        });
+        receiver
+    }
+}
+
+/// The reading thread holds a copy of the socket,
+/// it has to be shut down for the server to see the client leave.
+impl Drop for Client {
+    fn drop(&mut self) {
+        let _ = self.writer.shutdown(Shutdown::Both);
+    }
+}
+
+/// A [Game] played at a table of the server,
+/// mirrored from the positions sent by the server
+///
+/// The mirrored [Game] is only what the client may see:
+/// the hidden cards are seen from their back and it has no seed.
+#[derive(Debug, Default)]
+pub struct Mirror {
+    /// The mirrored game once it has started
+    pub game: Option<Game>,
+    /// Seat of the client, [None] for a spectator
+    pub seat: Option<PlayerId>,
+    /// Token to sit back after a disconnection
+    pub token: Option<u64>,
+    /// The table has been closed by the server
+    pub closed: bool,
+}
+
+impl Mirror {
+    /// Updates the mirrored [Game] from a message of the server
+    ///
+    /// Returns the message to send back when a position
+    /// cannot be read or differs from its checksum.
+    ///
+    /// Remark: the [Event](crate::event::Event)s played are not applied to the mirror, it cannot draw
+    /// the hidden cards. Every position replaces the mirrored [Game], the checksum
+    /// only tells a position garbled on the way.
+    pub fn update(&mut self, message: ServerMessage) -> Option<ClientMessage> {
+        use ServerMessage::*;
+        match message {
+            Joined { seat, token, .. } => {
+                self.seat = Some(seat);
+                self.token = Some(token);
+            }
+            Position { checksum, code } => match Game::from_position_code(&code) {
+                Ok(mut game) if game.state_hash() == checksum => {
+                    // The log is not part of the position
+                    if let Some(mirrored) = self.game.take() {
+                        game.log = mirrored.log;
+                    }
+                    self.game = Some(game);
+                }
+                _ => return Some(ClientMessage::Resync),
+            },
+            Logged(entry) => {
+                if let Some(game) = &mut self.game {
+                    game.log.push(entry);
+                }
+            }
+            Closed => self.closed = true,
+            _ => (),
+        }
+        None
+    }
+}
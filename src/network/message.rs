@@ -0,0 +1,336 @@
+use crate::{
+    card::Card,
+    event::Event,
+    log::{LogEntry, Logged},
+    player_id::PlayerId,
+};
+use std::{fmt::Display, str::FromStr};
+
+/// Message sent by a client to the server
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ClientMessage {
+    /// Ask for the list of the open tables
+    List,
+    /// Create a new table with a name
+    Create(String),
+    /// Sit on a seat of a table
+    Join { table: usize, seat: PlayerId },
+    /// Watch a table without playing
+    ///
+    /// With a `delay` in seconds the events reach the spectator late
+    /// and the hands are revealed, for example when casting a game.
+    Spectate { table: usize, delay: u64 },
+    /// Sit back on a seat after a disconnection
+    Reconnect { token: u64 },
+    /// Ask for the position again to resynchronize the mirrored [Game](crate::game::Game)
+    Resync,
+    /// Leave the current table
+    Leave,
+    /// Play an [Event] at the current table
+    Play(Event),
+}
+
+/// Message sent by the server to a client
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ServerMessage {
+    /// The open tables of the lobby
+    Tables(Vec<TableInfo>),
+    /// A table has been created
+    Created(usize),
+    /// The client sits at a table,
+    /// the `token` lets the client come back after a disconnection
+    Joined {
+        table: usize,
+        seat: PlayerId,
+        token: u64,
+    },
+    /// The client watches a table
+    Watching { table: usize, delay: u64 },
+    /// An [Event] has been accepted at the table
+    Played(Event),
+    /// The [position code](crate::game::Game::position_code) of the game
+    /// as [seen](crate::game::Game::seen_by) by the client, with its
+    /// [state hash](crate::game::Rules::state_hash) as a checksum
+    Position { checksum: u64, code: String },
+    /// An entry of the log of the game
+    Logged(LogEntry),
+    /// The table has been closed
+    Closed,
+    /// The last message of the client has been refused
+    Error(String),
+}
+
+/// Description of an open table of the lobby
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TableInfo {
+    pub id: usize,
+    pub name: String,
+    /// Seats still available at the table
+    pub free: Vec<PlayerId>,
+}
+
+/// Name of a table as a single word usable in the protocol
+pub fn table_name(name: &str) -> String {
+    let name: String = name
+        .trim()
+        .chars()
+        .map(|c| {
+            if c.is_whitespace() || c == ':' || c == ',' {
+                '_'
+            } else {
+                c
+            }
+        })
+        .collect();
+    if name.is_empty() {
+        String::from("table")
+    } else {
+        name
+    }
+}
+
+fn parse_index(word: Option<&str>) -> Result<usize, String> {
+    match word {
+        Some(word) => word
+            .parse()
+            .map_err(|_| format!("'{word}' is not a table number")),
+        None => Err(String::from("missing table number")),
+    }
+}
+
+fn parse_number<T: FromStr>(word: &str) -> Result<T, String> {
+    word.trim()
+        .parse()
+        .map_err(|_| format!("'{word}' is not a number"))
+}
+
+/// Parses a delay in seconds, no delay when it is missing
+fn parse_delay(word: Option<&str>) -> Result<u64, String> {
+    match word {
+        Some(word) => word.parse().map_err(|_| format!("'{word}' is not a delay")),
+        None => Ok(0),
+    }
+}
+
+/// Name of a card of the [collection](Card::collection)
+fn parse_card_name(name: &str) -> Result<&'static str, String> {
+    Card::collection()
+        .iter()
+        .map(|card| card.name())
+        .find(|card| *card == name)
+        .ok_or_else(|| format!("'{name}' is not a card"))
+}
+
+/// A [LogEntry] as `<turn> <player> <what>`, the name of a card comes last
+struct LogLine<'a>(&'a LogEntry);
+
+impl Display for LogLine<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        use Logged::*;
+        let LogEntry {
+            turn,
+            player,
+            logged,
+        } = self.0;
+        write!(f, "{turn} {player} ")?;
+        match logged {
+            Bought { card, gold } => write!(f, "bought {gold} {card}"),
+            Played(card) => write!(f, "played {card}"),
+            Scrapped(card) => write!(f, "scrapped {card}"),
+            Discarded(card) => write!(f, "discarded {card}"),
+            Copied(card) => write!(f, "copied {card}"),
+            ChoseNothing => write!(f, "chose-nothing"),
+            Attacked { attack, authority } => write!(f, "attacked {attack} {authority}"),
+            Won => write!(f, "won"),
+            EndedTurn => write!(f, "ended"),
+        }
+    }
+}
+
+fn parse_log_line(s: &str) -> Result<LogEntry, String> {
+    use Logged::*;
+    let mut words = s.trim().splitn(4, ' ');
+    let turn = parse_number(words.next().unwrap_or(""))?;
+    let player = words.next().ok_or("missing player")?.parse()?;
+    let what = words.next().ok_or("missing log entry")?;
+    let rest = words.next().unwrap_or("");
+    let logged = match what {
+        "bought" => {
+            let (gold, card) = rest.split_once(' ').unwrap_or((rest, ""));
+            Bought {
+                card: parse_card_name(card)?,
+                gold: parse_number(gold)?,
+            }
+        }
+        "played" => Played(parse_card_name(rest)?),
+        "scrapped" => Scrapped(parse_card_name(rest)?),
+        "discarded" => Discarded(parse_card_name(rest)?),
+        "copied" => Copied(parse_card_name(rest)?),
+        "chose-nothing" => ChoseNothing,
+        "attacked" => {
+            let (attack, authority) = rest.split_once(' ').unwrap_or((rest, ""));
+            Attacked {
+                attack: parse_number(attack)?,
+                authority: parse_number(authority)?,
+            }
+        }
+        "won" => Won,
+        "ended" => EndedTurn,
+        _ => return Err(format!("unknown log entry '{what}'")),
+    };
+    Ok(LogEntry {
+        turn,
+        player,
+        logged,
+    })
+}
+
+impl Display for ClientMessage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        use ClientMessage::*;
+        match self {
+            List => write!(f, "list"),
+            Create(name) => write!(f, "create {}", table_name(name)),
+            Join { table, seat } => write!(f, "join {table} {seat}"),
+            Spectate { table, delay: 0 } => write!(f, "spectate {table}"),
+            Spectate { table, delay } => write!(f, "spectate {table} {delay}"),
+            Reconnect { token } => write!(f, "reconnect {token}"),
+            Resync => write!(f, "resync"),
+            Leave => write!(f, "leave"),
+            Play(event) => write!(f, "event {event}"),
+        }
+    }
+}
+
+impl FromStr for ClientMessage {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        use ClientMessage::*;
+        let (word, rest) = s.trim().split_once(' ').unwrap_or((s.trim(), ""));
+        match word {
+            "list" => Ok(List),
+            "create" => Ok(Create(table_name(rest))),
+            "join" => {
+                let mut words = rest.split_whitespace();
+                let table = parse_index(words.next())?;
+                let seat = words.next().ok_or("missing seat")?.parse()?;
+                Ok(Join { table, seat })
+            }
+            "spectate" => {
+                let mut words = rest.split_whitespace();
+                let table = parse_index(words.next())?;
+                let delay = parse_delay(words.next())?;
+                Ok(Spectate { table, delay })
+            }
+            "reconnect" => Ok(Reconnect {
+                token: parse_number(rest)?,
+            }),
+            "resync" => Ok(Resync),
+            "leave" => Ok(Leave),
+            "event" => Ok(Play(rest.parse()?)),
+            _ => Err(format!("unknown message '{word}'")),
+        }
+    }
+}
+
+impl Display for TableInfo {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}:", self.id, table_name(&self.name))?;
+        for (i, seat) in self.free.iter().enumerate() {
+            if i != 0 {
+                write!(f, ",")?;
+            }
+            write!(f, "{seat}")?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for TableInfo {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = s.split(':');
+        let (Some(id), Some(name), Some(free), None) =
+            (parts.next(), parts.next(), parts.next(), parts.next())
+        else {
+            return Err(format!("'{s}' is not a table 'id:name:seats'"));
+        };
+        Ok(Self {
+            id: parse_index(Some(id))?,
+            name: String::from(name),
+            free: free
+                .split(',')
+                .filter(|seat| !seat.is_empty())
+                .map(str::parse)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+impl Display for ServerMessage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        use ServerMessage::*;
+        match self {
+            Tables(tables) => {
+                write!(f, "tables")?;
+                for table in tables {
+                    write!(f, " {table}")?;
+                }
+                Ok(())
+            }
+            Created(table) => write!(f, "created {table}"),
+            Joined { table, seat, token } => write!(f, "joined {table} {seat} {token}"),
+            Watching { table, delay } => write!(f, "watching {table} {delay}"),
+            Played(event) => write!(f, "event {event}"),
+            Position { checksum, code } => write!(f, "position {checksum} {code}"),
+            Logged(entry) => write!(f, "log {}", LogLine(entry)),
+            Closed => write!(f, "closed"),
+            // An error always fits on one line
+            Error(error) => write!(f, "error {}", error.replace('\n', " ")),
+        }
+    }
+}
+
+impl FromStr for ServerMessage {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        use ServerMessage::*;
+        let (word, rest) = s.trim().split_once(' ').unwrap_or((s.trim(), ""));
+        match word {
+            "tables" => Ok(Tables(
+                rest.split_whitespace()
+                    .map(str::parse)
+                    .collect::<Result<_, _>>()?,
+            )),
+            "created" => Ok(Created(parse_index(Some(rest))?)),
+            "joined" => {
+                let mut words = rest.split_whitespace();
+                let table = parse_index(words.next())?;
+                let seat = words.next().ok_or("missing seat")?.parse()?;
+                let token = parse_number(words.next().unwrap_or(""))?;
+                Ok(Joined { table, seat, token })
+            }
+            "watching" => {
+                let mut words = rest.split_whitespace();
+                let table = parse_index(words.next())?;
+                let delay = parse_delay(words.next())?;
+                Ok(Watching { table, delay })
+            }
+            "event" => Ok(Played(rest.parse()?)),
+            "position" => {
+                let (checksum, code) = rest.split_once(' ').unwrap_or((rest, ""));
+                Ok(Position {
+                    checksum: parse_number(checksum)?,
+                    code: String::from(code.trim()),
+                })
+            }
+            "log" => Ok(Logged(parse_log_line(rest)?)),
+            "closed" => Ok(Closed),
+            "error" => Ok(Error(String::from(rest))),
+            _ => Err(format!("unknown message '{word}'")),
+        }
+    }
+}
@@ -0,0 +1,672 @@
+use crate::{
+    event::Event,
+    game::{Game, Viewer},
+    network::message::{ClientMessage, ServerMessage, TableInfo, table_name},
+    player_id::PlayerId,
+    state::State,
+};
+use std::{
+    collections::{BTreeMap, VecDeque},
+    io::{self, BufRead, BufReader, Write},
+    net::{SocketAddr, TcpListener, TcpStream, ToSocketAddrs},
+    sync::{
+        Arc, Mutex,
+        mpsc::{Receiver, RecvTimeoutError, Sender, channel},
+    },
+    thread::{self, JoinHandle},
+    time::{Duration, Instant},
+};
+
+/// Server hosting a lobby of tables
+///
+/// Every client is handled in its own thread
+/// and every table runs its [Game] in its own thread.
+pub struct Server {
+    listener: TcpListener,
+    lobby: Arc<Mutex<Lobby>>,
+}
+
+impl Server {
+    pub fn bind<A: ToSocketAddrs>(addr: A) -> io::Result<Self> {
+        Ok(Self {
+            listener: TcpListener::bind(addr)?,
+            lobby: Arc::default(),
+        })
+    }
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.listener.local_addr()
+    }
+    /// Accept clients until the listener fails
+    pub fn run(self) -> io::Result<()> {
+        for stream in self.listener.incoming() {
+            let stream = stream?;
+            let lobby = self.lobby.clone();
+            thread::spawn(move || handle_client(stream, lobby));
+        }
+        Ok(())
+    }
+    /// Sets how long a table waits for its first player, and a started game
+    /// for its players to reconnect once they are all gone, see [ABANDONED_AFTER]
+    pub fn abandoned_after(self, timeout: Duration) -> Self {
+        self.lobby.lock().unwrap().abandoned_after = timeout;
+        self
+    }
+    /// Run the [Server] in a background thread
+    pub fn spawn(self) -> JoinHandle<io::Result<()>> {
+        thread::spawn(move || self.run())
+    }
+}
+
+/// Writing half of the connection with a client
+#[derive(Debug, Clone)]
+struct Peer(Arc<Mutex<TcpStream>>);
+
+impl Peer {
+    fn send(&self, message: &ServerMessage) -> io::Result<()> {
+        let mut stream = self.0.lock().unwrap();
+        writeln!(stream, "{message}")
+    }
+    fn is(&self, other: &Peer) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+/// A client watching a [Table]
+///
+/// The messages reach a delayed spectator late, with the hands revealed.
+struct Spectator {
+    peer: Peer,
+    delay: Duration,
+    /// Messages waiting for the delay with the time they are due
+    queue: VecDeque<(Instant, ServerMessage)>,
+}
+
+impl Spectator {
+    fn new(peer: Peer, delay: u64) -> Self {
+        Self {
+            peer,
+            delay: Duration::from_secs(delay),
+            queue: VecDeque::new(),
+        }
+    }
+    /// What the spectator may see of the [Game]
+    fn viewer(&self) -> Viewer {
+        if self.delay.is_zero() {
+            Viewer::Spectator
+        } else {
+            Viewer::Delayed
+        }
+    }
+    /// Sends a message that happened at a given time, once the delay is over
+    fn send(&mut self, at: Instant, message: &ServerMessage) {
+        if self.delay.is_zero() {
+            let _ = self.peer.send(message);
+        } else {
+            self.queue.push_back((at + self.delay, message.clone()));
+        }
+    }
+    /// Sends the messages that are due, returns when the next one is due
+    fn flush(&mut self, now: Instant) -> Option<Instant> {
+        while let Some((due, _)) = self.queue.front()
+            && *due <= now
+            && let Some((_, message)) = self.queue.pop_front()
+        {
+            let _ = self.peer.send(&message);
+        }
+        self.queue.front().map(|(due, _)| *due)
+    }
+}
+
+/// Time a table without players is kept by default before it is closed
+pub const ABANDONED_AFTER: Duration = Duration::from_secs(300);
+
+#[derive(Debug)]
+struct Lobby {
+    next_id: usize,
+    tables: BTreeMap<usize, TableEntry>,
+    /// Time a table without players is kept before it is closed
+    abandoned_after: Duration,
+}
+
+impl Default for Lobby {
+    fn default() -> Self {
+        Self {
+            next_id: 0,
+            tables: BTreeMap::new(),
+            abandoned_after: ABANDONED_AFTER,
+        }
+    }
+}
+
+/// What the lobby knows about a running [Table]
+#[derive(Debug)]
+struct TableEntry {
+    name: String,
+    /// Tokens of the seats already taken,
+    /// a disconnected player keeps its seat
+    tokens: [Option<u64>; 2],
+    sender: Sender<TableCommand>,
+}
+
+impl Lobby {
+    fn infos(&self) -> Vec<TableInfo> {
+        self.tables
+            .iter()
+            .map(|(&id, table)| TableInfo {
+                id,
+                name: table.name.clone(),
+                free: [PlayerId::First, PlayerId::Second]
+                    .into_iter()
+                    .filter(|&seat| table.tokens[seat].is_none())
+                    .collect(),
+            })
+            .collect()
+    }
+    /// Creates a new table and starts its thread
+    fn create(lobby: &Arc<Mutex<Lobby>>, name: &str) -> usize {
+        let (sender, receiver) = channel();
+        let mut guard = lobby.lock().unwrap();
+        let id = guard.next_id;
+        guard.next_id += 1;
+        guard.tables.insert(
+            id,
+            TableEntry {
+                name: table_name(name),
+                tokens: [None, None],
+                sender,
+            },
+        );
+        let table = Table {
+            id,
+            lobby: lobby.clone(),
+            game: None,
+            history: vec![],
+            peers: [None, None],
+            spectators: vec![],
+            receiver,
+            abandoned_after: guard.abandoned_after,
+            // Nobody may ever join the table
+            abandoned: Some(Instant::now() + guard.abandoned_after),
+        };
+        thread::spawn(move || table.run());
+        id
+    }
+    fn sender(&self, table: usize) -> Result<Sender<TableCommand>, String> {
+        match self.tables.get(&table) {
+            Some(entry) => Ok(entry.sender.clone()),
+            None => Err(format!("there is no table {table}")),
+        }
+    }
+    /// Takes a seat at a table, returns the token of the seat
+    fn join(
+        &mut self,
+        table: usize,
+        seat: PlayerId,
+    ) -> Result<(Sender<TableCommand>, u64), String> {
+        let Some(entry) = self.tables.get_mut(&table) else {
+            return Err(format!("there is no table {table}"));
+        };
+        if entry.tokens[seat].is_some() {
+            return Err(format!("the seat {seat} of table {table} is taken"));
+        }
+        let token = rand::random();
+        entry.tokens[seat] = Some(token);
+        Ok((entry.sender.clone(), token))
+    }
+    /// Finds the seat of a token
+    fn reconnect(&self, token: u64) -> Result<(Sender<TableCommand>, PlayerId), String> {
+        self.tables
+            .values()
+            .find_map(|entry| {
+                [PlayerId::First, PlayerId::Second]
+                    .into_iter()
+                    .find(|&seat| entry.tokens[seat] == Some(token))
+                    .map(|seat| (entry.sender.clone(), seat))
+            })
+            .ok_or_else(|| String::from("no seat has this token"))
+    }
+}
+
+/// Commands sent by the clients to their [Table]
+#[derive(Debug)]
+enum TableCommand {
+    Sit {
+        seat: PlayerId,
+        peer: Peer,
+        token: u64,
+    },
+    Play {
+        seat: PlayerId,
+        peer: Peer,
+        event: Event,
+    },
+    /// The player left the table on purpose
+    Leave {
+        seat: PlayerId,
+        peer: Peer,
+    },
+    /// The connection with the player is lost, its seat is kept
+    Disconnect {
+        seat: PlayerId,
+        peer: Peer,
+    },
+    Resync {
+        peer: Peer,
+    },
+    Watch {
+        peer: Peer,
+        delay: u64,
+    },
+    Unwatch {
+        peer: Peer,
+    },
+}
+
+/// A table of the lobby running one [Game]
+///
+/// The [Game], its seed and its random number generator never leave the table:
+/// the clients receive the [Game] as they may see it.
+struct Table {
+    id: usize,
+    lobby: Arc<Mutex<Lobby>>,
+    game: Option<Game>,
+    /// The messages sent to the delayed spectators with the time they happened,
+    /// sent again to the delayed spectators arriving late
+    history: Vec<(Instant, ServerMessage)>,
+    peers: [Option<Peer>; 2],
+    spectators: Vec<Spectator>,
+    receiver: Receiver<TableCommand>,
+    abandoned_after: Duration,
+    /// When the table is closed if no player sits back before
+    abandoned: Option<Instant>,
+}
+
+/// The [Game] as a [Viewer] may see it
+fn position(game: &Game, viewer: Viewer) -> ServerMessage {
+    let seen = game.seen_by(viewer);
+    ServerMessage::Position {
+        checksum: seen.state_hash(),
+        code: seen.position_code().to_string(),
+    }
+}
+
+/// The [Game] as a [Viewer] may see it and its log, for a client arriving late
+fn catch_up(game: &Game, viewer: Viewer) -> impl Iterator<Item = ServerMessage> {
+    [position(game, viewer)]
+        .into_iter()
+        .chain(game.log.iter().copied().map(ServerMessage::Logged))
+}
+
+impl Table {
+    fn broadcast(&mut self, message: &ServerMessage) {
+        for peer in self.peers.iter().flatten() {
+            let _ = peer.send(message);
+        }
+        let now = Instant::now();
+        for spectator in &mut self.spectators {
+            spectator.send(now, message);
+        }
+    }
+    /// Sends to everyone at the table what they may see of the [Game],
+    /// after the messages of what happened since the last position
+    fn broadcast_game(&mut self, happened: Vec<ServerMessage>) {
+        let Some(game) = &self.game else {
+            return;
+        };
+        for seat in [PlayerId::First, PlayerId::Second] {
+            if let Some(peer) = &self.peers[seat] {
+                for message in &happened {
+                    let _ = peer.send(message);
+                }
+                let _ = peer.send(&position(game, Viewer::Seat(seat)));
+            }
+        }
+        let now = Instant::now();
+        for viewer in [Viewer::Spectator, Viewer::Delayed] {
+            let seen = happened.iter().cloned().chain([position(game, viewer)]);
+            for message in seen {
+                for spectator in &mut self.spectators {
+                    if spectator.viewer() == viewer {
+                        spectator.send(now, &message);
+                    }
+                }
+                if viewer == Viewer::Delayed {
+                    self.history.push((now, message));
+                }
+            }
+        }
+    }
+    /// Sends the delayed messages that are due, returns when the next one is due
+    fn flush(&mut self) -> Option<Instant> {
+        let now = Instant::now();
+        self.spectators
+            .iter_mut()
+            .filter_map(|spectator| spectator.flush(now))
+            .min()
+    }
+    fn reply(&self, seat: PlayerId, message: ServerMessage) {
+        if let Some(peer) = &self.peers[seat] {
+            let _ = peer.send(&message);
+        }
+    }
+    fn is_seated(&self, seat: PlayerId, peer: &Peer) -> bool {
+        self.peers[seat]
+            .as_ref()
+            .is_some_and(|seated| seated.is(peer))
+    }
+    fn free_seat(&self, seat: PlayerId) {
+        if let Some(entry) = self.lobby.lock().unwrap().tables.get_mut(&self.id) {
+            entry.tokens[seat] = None;
+        }
+    }
+    /// Handles the commands of the clients until the table is over
+    fn run(mut self) {
+        use TableCommand::*;
+        loop {
+            // Wait for a command, for the next delayed message
+            // or for the players to be gone for too long
+            let command = match self.flush().into_iter().chain(self.abandoned).min() {
+                Some(due) => {
+                    match self
+                        .receiver
+                        .recv_timeout(due.saturating_duration_since(Instant::now()))
+                    {
+                        Ok(command) => command,
+                        Err(RecvTimeoutError::Timeout)
+                            if self.abandoned.is_some_and(|at| at <= Instant::now()) =>
+                        {
+                            break;
+                        }
+                        Err(RecvTimeoutError::Timeout) => continue,
+                        Err(RecvTimeoutError::Disconnected) => break,
+                    }
+                }
+                None => match self.receiver.recv() {
+                    Ok(command) => command,
+                    Err(_) => break,
+                },
+            };
+            match command {
+                Sit { seat, peer, token } => {
+                    let _ = peer.send(&ServerMessage::Joined {
+                        table: self.id,
+                        seat,
+                        token,
+                    });
+                    // A player coming back receives the game and its log
+                    if let Some(game) = &self.game {
+                        for message in catch_up(game, Viewer::Seat(seat)) {
+                            let _ = peer.send(&message);
+                        }
+                    }
+                    self.peers[seat] = Some(peer);
+                    self.abandoned = None;
+                    if self.game.is_none() && self.peers.iter().all(Option::is_some) {
+                        self.game = Some(Game::new());
+                        self.broadcast_game(vec![]);
+                    }
+                }
+                Play { seat, peer, event } => {
+                    // A player replaced by its reconnection does not play anymore
+                    if !self.is_seated(seat, &peer) {
+                        let _ = peer.send(&ServerMessage::Error("you are not seated".into()));
+                        continue;
+                    }
+                    let Some(game) = &mut self.game else {
+                        self.reply(
+                            seat,
+                            ServerMessage::Error("the game has not started".into()),
+                        );
+                        continue;
+                    };
+                    let logged = game.log.len();
+                    if game.current_player != seat {
+                        self.reply(seat, ServerMessage::Error("it is not your turn".into()));
+                    } else if game.apply_event(event.clone()).is_err() {
+                        self.reply(seat, ServerMessage::Error(format!("'{event}' rejected")));
+                    } else {
+                        let over = matches!(game.state, State::WonBy(_));
+                        let happened = [ServerMessage::Played(event)]
+                            .into_iter()
+                            .chain(
+                                game.log[logged..]
+                                    .iter()
+                                    .copied()
+                                    .map(ServerMessage::Logged),
+                            )
+                            .collect();
+                        self.broadcast_game(happened);
+                        if over {
+                            break;
+                        }
+                    }
+                }
+                Leave { seat, peer } => {
+                    if !self.is_seated(seat, &peer) {
+                        continue;
+                    }
+                    self.peers[seat] = None;
+                    // A started game is over when a player leaves
+                    if self.game.is_some() || self.peers.iter().all(Option::is_none) {
+                        break;
+                    }
+                    self.free_seat(seat);
+                }
+                Disconnect { seat, peer } => {
+                    if !self.is_seated(seat, &peer) {
+                        continue;
+                    }
+                    self.peers[seat] = None;
+                    // The seat is kept for a started game,
+                    // until the players are gone for too long
+                    if self.game.is_none() {
+                        self.free_seat(seat);
+                        if self.peers.iter().all(Option::is_none) {
+                            break;
+                        }
+                    } else if self.peers.iter().all(Option::is_none) {
+                        self.abandoned = Some(Instant::now() + self.abandoned_after);
+                    }
+                }
+                Resync { peer } => {
+                    let Some(game) = &self.game else {
+                        continue;
+                    };
+                    for seat in [PlayerId::First, PlayerId::Second] {
+                        if self.is_seated(seat, &peer) {
+                            let _ = peer.send(&position(game, Viewer::Seat(seat)));
+                        }
+                    }
+                    let now = Instant::now();
+                    for spectator in &mut self.spectators {
+                        if spectator.peer.is(&peer) {
+                            spectator.send(now, &position(game, spectator.viewer()));
+                        }
+                    }
+                }
+                Watch { peer, delay } => {
+                    let mut spectator = Spectator::new(peer, delay);
+                    let _ = spectator.peer.send(&ServerMessage::Watching {
+                        table: self.id,
+                        delay,
+                    });
+                    // A live spectator receives the game and its log,
+                    // a delayed one the whole game late
+                    if spectator.viewer() == Viewer::Delayed {
+                        for (at, message) in &self.history {
+                            spectator.send(*at, message);
+                        }
+                    } else if let Some(game) = &self.game {
+                        let now = Instant::now();
+                        for message in catch_up(game, Viewer::Spectator) {
+                            spectator.send(now, &message);
+                        }
+                    }
+                    self.spectators.push(spectator);
+                }
+                Unwatch { peer } => self
+                    .spectators
+                    .retain(|spectator| !spectator.peer.is(&peer)),
+            }
+        }
+        self.lobby.lock().unwrap().tables.remove(&self.id);
+        self.broadcast(&ServerMessage::Closed);
+        // The delayed spectators see the end of the game
+        while let Some(due) = self.flush() {
+            thread::sleep(due.saturating_duration_since(Instant::now()));
+        }
+    }
+}
+
+/// Place of a client at a table
+enum Place {
+    Seat {
+        seat: PlayerId,
+        sender: Sender<TableCommand>,
+    },
+    Spectator {
+        sender: Sender<TableCommand>,
+    },
+}
+
+impl Place {
+    fn sender(&self) -> &Sender<TableCommand> {
+        match self {
+            Place::Seat { sender, .. } | Place::Spectator { sender } => sender,
+        }
+    }
+    fn leave(self, peer: &Peer) {
+        let peer = peer.clone();
+        let _ = match self {
+            Place::Seat { seat, sender } => sender.send(TableCommand::Leave { seat, peer }),
+            Place::Spectator { sender } => sender.send(TableCommand::Unwatch { peer }),
+        };
+    }
+    fn disconnect(self, peer: &Peer) {
+        let peer = peer.clone();
+        let _ = match self {
+            Place::Seat { seat, sender } => sender.send(TableCommand::Disconnect { seat, peer }),
+            Place::Spectator { sender } => sender.send(TableCommand::Unwatch { peer }),
+        };
+    }
+}
+
+/// Sits a client on a seat of a table
+fn sit(
+    sender: Sender<TableCommand>,
+    seat: PlayerId,
+    token: u64,
+    peer: &Peer,
+) -> Result<Place, String> {
+    let sit = TableCommand::Sit {
+        seat,
+        peer: peer.clone(),
+        token,
+    };
+    match sender.send(sit) {
+        Ok(()) => Ok(Place::Seat { seat, sender }),
+        Err(_) => Err(String::from("the table is closed")),
+    }
+}
+
+/// Reads the messages of a client until it disconnects
+fn handle_client(stream: TcpStream, lobby: Arc<Mutex<Lobby>>) {
+    use ClientMessage::*;
+    let Ok(writer) = stream.try_clone() else {
+        return;
+    };
+    let peer = Peer(Arc::new(Mutex::new(writer)));
+    let mut place: Option<Place> = None;
+
+    for line in BufReader::new(stream).lines() {
+        let Ok(line) = line else {
+            break;
+        };
+        let message = line.parse::<ClientMessage>();
+        // Changing of table is leaving the current one
+        if let Ok(Join { .. } | Reconnect { .. } | Spectate { .. } | Leave) = message
+            && let Some(place) = place.take()
+        {
+            place.leave(&peer);
+        }
+        let reply = match message {
+            Err(error) => Some(ServerMessage::Error(error)),
+            Ok(List) => Some(ServerMessage::Tables(lobby.lock().unwrap().infos())),
+            Ok(Create(name)) => Some(ServerMessage::Created(Lobby::create(&lobby, &name))),
+            Ok(Join { table, seat }) => {
+                let joined = lobby.lock().unwrap().join(table, seat);
+                match joined.and_then(|(sender, token)| sit(sender, seat, token, &peer)) {
+                    Ok(seated) => {
+                        place = Some(seated);
+                        None
+                    }
+                    Err(error) => Some(ServerMessage::Error(error)),
+                }
+            }
+            Ok(Reconnect { token }) => {
+                let found = lobby.lock().unwrap().reconnect(token);
+                match found.and_then(|(sender, seat)| sit(sender, seat, token, &peer)) {
+                    Ok(seated) => {
+                        place = Some(seated);
+                        None
+                    }
+                    Err(error) => Some(ServerMessage::Error(error)),
+                }
+            }
+            Ok(Spectate { table, delay }) => {
+                let sender = lobby.lock().unwrap().sender(table);
+                match sender {
+                    Ok(sender) => {
+                        let watch = TableCommand::Watch {
+                            peer: peer.clone(),
+                            delay,
+                        };
+                        if sender.send(watch).is_ok() {
+                            place = Some(Place::Spectator { sender });
+                            None
+                        } else {
+                            Some(ServerMessage::Error(format!("table {table} is closed")))
+                        }
+                    }
+                    Err(error) => Some(ServerMessage::Error(error)),
+                }
+            }
+            Ok(Resync) => match &place {
+                Some(place) => {
+                    let resync = TableCommand::Resync { peer: peer.clone() };
+                    let _ = place.sender().send(resync);
+                    None
+                }
+                None => Some(ServerMessage::Error("you are not at a table".into())),
+            },
+            Ok(Leave) => None,
+            Ok(Play(event)) => match &place {
+                Some(Place::Seat { seat, sender }) => {
+                    let play = TableCommand::Play {
+                        seat: *seat,
+                        peer: peer.clone(),
+                        event,
+                    };
+                    if sender.send(play).is_err() {
+                        place = None;
+                        Some(ServerMessage::Error("the table is closed".into()))
+                    } else {
+                        None
+                    }
+                }
+                Some(Place::Spectator { .. }) => {
+                    Some(ServerMessage::Error("spectators cannot play".into()))
+                }
+                None => Some(ServerMessage::Error("you are not at a table".into())),
+            },
+        };
+        if let Some(reply) = reply
+            && peer.send(&reply).is_err()
+        {
+            break;
+        }
+    }
+    if let Some(place) = place {
+        place.disconnect(&peer);
+    }
+}
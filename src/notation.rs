@@ -0,0 +1,329 @@
+//! A portable text notation of whole games, like the PGN of chess
+//!
+//! A header of tags is followed by one line per turn, the cards are named:
+//! ```text
+//! [Seed "42"]
+//! [Set "core"]
+//! [First "Alice"]
+//! [Second "?"]
+//! [Result "*"]
+//!
+//! 1. Play all, Buy Cutter, End
+//! 2. Play Scout, Play Scout (2), Play Viper, Attack, End
+//! 3. Play Missile Bot, Scrap Viper from discard, End
+//! ```
+//! A name is followed by the number of the copy when a deck holds several copies
+//! before it, the choices name the deck of each card: `from hand`, `from opponent discard`...
+//! The players are `?` when unknown and the result is `1st`, `2nd` or `*` for an unfinished game.
+//!
+//! The moves are resolved against the [Game] replayed from its seed:
+//! the same cards are chosen and the replayed game is identical to the original one.
+
+use crate::{
+    card::CardId,
+    event::Event,
+    game::Game,
+    player_id::PlayerId,
+    selection::{GamePosition, Location},
+    state::State,
+};
+use std::{fmt::Display, str::FromStr};
+
+/// The only set of cards of the game, in the `Set` tag
+pub const CARD_SET: &str = "core";
+
+/// A [Game] written in the notation
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Notation {
+    pub seed: u64,
+    /// Names of the players, indexed by [PlayerId], `?` when unknown
+    pub players: [String; 2],
+    /// The accepted events of the game
+    pub events: Vec<Event>,
+}
+
+impl Notation {
+    /// The notation of the events of a `seed` with unknown players,
+    /// the rejected events are left out
+    pub fn new(seed: u64, events: &[Event]) -> Self {
+        let mut game = Game::with_seed(seed);
+        let events = events
+            .iter()
+            .filter(|event| game.apply_event((*event).clone()).is_ok())
+            .cloned()
+            .collect();
+        Self {
+            seed,
+            players: [String::from("?"), String::from("?")],
+            events,
+        }
+    }
+    /// Plays the events on a new [Game]
+    pub fn replay(&self) -> Game {
+        let mut game = Game::with_seed(self.seed);
+        for event in &self.events {
+            let _ = game.apply_event(event.clone());
+        }
+        game
+    }
+}
+
+impl Game {
+    /// Creates the [Notation] of a [Game]
+    pub fn notation(&self) -> Notation {
+        Notation::new(self.seed, &self.historic)
+    }
+}
+
+/// A card as written in a move, `Scout (2)` for the second Scout of its deck,
+/// `#12` for a card out of the decks
+fn card_text(game: &Game, id: CardId) -> (Option<GamePosition>, String) {
+    let Some(position) = game.position_of(id) else {
+        return (None, id.to_string());
+    };
+    let deck = game.zone(position.player, position.loc);
+    let name = deck[position.index].name();
+    let copy = deck
+        .iter()
+        .take(position.index)
+        .filter(|card| card.name() == name)
+        .count();
+    match copy {
+        0 => (Some(position), String::from(name)),
+        _ => (Some(position), format!("{name} ({})", copy + 1)),
+    }
+}
+
+/// Finds the card written by [card_text] in a deck of a player
+fn card_id(game: &Game, player: PlayerId, loc: Location, text: &str) -> Result<CardId, String> {
+    if text.starts_with('#') {
+        return text.parse();
+    }
+    let (name, copy) = match text
+        .strip_suffix(')')
+        .and_then(|text| text.rsplit_once(" ("))
+    {
+        Some((name, copy)) => {
+            let copy: usize = copy
+                .parse()
+                .map_err(|_| format!("'{copy}' is not the number of a copy"))?;
+            (name, copy.max(1))
+        }
+        None => (text, 1),
+    };
+    game.zone(player, loc)
+        .iter()
+        .filter(|card| card.name() == name)
+        .nth(copy - 1)
+        .map(|card| card.id)
+        .ok_or_else(|| format!("no {text} in the {loc}"))
+}
+
+/// The verb of a choice in a [State]
+fn choice_verb(state: &State) -> Option<&'static str> {
+    match state {
+        State::Scraping { .. } => Some("Scrap"),
+        State::Discarding { .. } => Some("Discard"),
+        State::Copy => Some("Copy"),
+        State::Playing | State::WonBy(_) => None,
+    }
+}
+
+/// A card of a deck of the current player or of the shop, `#12` when it is elsewhere
+fn card_in(game: &Game, id: CardId, loc: Location) -> String {
+    match card_text(game, id) {
+        (Some(position), text) if position.loc == loc && position.player == game.current_player => {
+            text
+        }
+        _ => id.to_string(),
+    }
+}
+
+/// Writes an [Event] of the current player as a move
+fn move_text(game: &Game, event: &Event) -> String {
+    match event {
+        Event::Buy(id) => format!("Buy {}", card_in(game, *id, Location::Shop)),
+        Event::BuyExplorer => String::from("Buy Explorer"),
+        Event::Play(id) => format!("Play {}", card_in(game, *id, Location::Hand)),
+        Event::PlayAll => String::from("Play all"),
+        Event::Activate(id) => format!("Activate {}", card_in(game, *id, Location::Played)),
+        Event::Choose(ids) => {
+            let verb = choice_verb(&game.state).unwrap_or("Choose");
+            if ids.is_empty() {
+                return format!("{verb} nothing");
+            }
+            let cards: Vec<_> = ids
+                .iter()
+                .map(|id| match card_text(game, *id) {
+                    (Some(position), text) => {
+                        let opponent = position.player != game.current_player
+                            && !matches!(position.loc, Location::Shop | Location::Explorer);
+                        let owner = if opponent { "opponent " } else { "" };
+                        format!("{text} from {owner}{}", position.loc.code())
+                    }
+                    (None, text) => text,
+                })
+                .collect();
+            format!("{verb} {}", cards.join(" and "))
+        }
+        Event::Attack => String::from("Attack"),
+        Event::NextTurn => String::from("End"),
+    }
+}
+
+/// Reads a move of the current player written by [move_text]
+fn parse_move(game: &Game, text: &str) -> Result<Event, String> {
+    let player = game.current_player;
+    let (verb, rest) = text.split_once(' ').unwrap_or((text, ""));
+    let event = match (verb, rest) {
+        ("Buy", "Explorer") => Event::BuyExplorer,
+        ("Buy", card) => Event::Buy(card_id(game, player, Location::Shop, card)?),
+        ("Play", "all") => Event::PlayAll,
+        ("Play", card) => Event::Play(card_id(game, player, Location::Hand, card)?),
+        ("Activate", card) => Event::Activate(card_id(game, player, Location::Played, card)?),
+        ("Scrap" | "Discard" | "Copy", choice) => {
+            if choice_verb(&game.state) != Some(verb) {
+                return Err(format!(
+                    "'{text}' while the player does not {}",
+                    verb.to_lowercase()
+                ));
+            }
+            if choice == "nothing" {
+                return Ok(Event::Choose(vec![]));
+            }
+            let ids = choice
+                .split(" and ")
+                .map(|card| {
+                    if card.starts_with('#') {
+                        return card.parse();
+                    }
+                    let (card, loc) = card
+                        .rsplit_once(" from ")
+                        .ok_or_else(|| format!("'{card}' does not tell where the card is"))?;
+                    match loc.strip_prefix("opponent ") {
+                        Some(loc) => card_id(game, player.other(), loc.parse()?, card),
+                        None => card_id(game, player, loc.parse()?, card),
+                    }
+                })
+                .collect::<Result<_, _>>()?;
+            Event::Choose(ids)
+        }
+        ("Attack", "") => Event::Attack,
+        ("End", "") => Event::NextTurn,
+        _ => return Err(format!("unknown move '{text}'")),
+    };
+    Ok(event)
+}
+
+/// `1st`, `2nd` or `*`
+fn result(game: &Game) -> String {
+    match game.state {
+        State::WonBy(winner) => winner.to_string(),
+        _ => String::from("*"),
+    }
+}
+
+impl Display for Notation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut game = Game::with_seed(self.seed);
+        let mut turns: Vec<(u32, Vec<String>)> = vec![];
+        for event in &self.events {
+            let (turn, text) = (game.turn, move_text(&game, event));
+            if game.apply_event(event.clone()).is_err() {
+                continue;
+            }
+            match turns.last_mut() {
+                Some((last, moves)) if *last == turn => moves.push(text),
+                _ => turns.push((turn, vec![text])),
+            }
+        }
+        writeln!(f, "[Seed \"{}\"]", self.seed)?;
+        writeln!(f, "[Set \"{CARD_SET}\"]")?;
+        writeln!(f, "[First \"{}\"]", self.players[PlayerId::First])?;
+        writeln!(f, "[Second \"{}\"]", self.players[PlayerId::Second])?;
+        writeln!(f, "[Result \"{}\"]", result(&game))?;
+        writeln!(f)?;
+        for (turn, moves) in turns {
+            writeln!(f, "{turn}. {}", moves.join(", "))?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Notation {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut seed = None;
+        let mut players = [String::from("?"), String::from("?")];
+        let mut expected_result = None;
+        let mut game = None;
+        let mut events = vec![];
+        for (i, line) in s.lines().enumerate() {
+            let error = |error: String| format!("line {}: {error}", i + 1);
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+            if let Some(tag) = line.strip_prefix('[') {
+                let (name, value) = tag
+                    .strip_suffix(']')
+                    .and_then(|tag| tag.split_once(' '))
+                    .and_then(|(name, value)| {
+                        Some((name, value.trim().strip_prefix('"')?.strip_suffix('"')?))
+                    })
+                    .ok_or_else(|| error(String::from("expected a tag like [Name \"value\"]")))?;
+                match name {
+                    "Seed" => {
+                        let value = value
+                            .parse()
+                            .map_err(|_| error(format!("'{value}' is not a seed")))?;
+                        seed = Some(value);
+                    }
+                    "Set" if value != CARD_SET => {
+                        return Err(error(format!("unknown set of cards '{value}'")));
+                    }
+                    "First" => players[PlayerId::First] = String::from(value),
+                    "Second" => players[PlayerId::Second] = String::from(value),
+                    "Result" => expected_result = Some(String::from(value)),
+                    // The unknown tags are kept by other tools
+                    _ => (),
+                }
+                continue;
+            }
+            let seed = seed.ok_or_else(|| error(String::from("missing the seed of the game")))?;
+            let game = game.get_or_insert_with(|| Game::with_seed(seed));
+            let (turn, moves) = line
+                .split_once(". ")
+                .ok_or_else(|| error(format!("expected a turn like '1. End', found '{line}'")))?;
+            if turn != game.turn.to_string() {
+                return Err(error(format!(
+                    "turn {turn} is turn {} of the game",
+                    game.turn
+                )));
+            }
+            for text in moves.split(", ") {
+                let event = parse_move(game, text.trim()).map_err(error)?;
+                game.apply_event(event.clone()).map_err(|rejected| {
+                    error(format!("'{text}' is not a legal move: {rejected}"))
+                })?;
+                events.push(event);
+            }
+        }
+        let seed = seed.ok_or_else(|| String::from("missing the seed of the game"))?;
+        let game = game.unwrap_or_else(|| Game::with_seed(seed));
+        if let Some(expected) = expected_result
+            && expected != result(&game)
+        {
+            return Err(format!(
+                "the result is '{expected}' but the moves give '{}'",
+                result(&game)
+            ));
+        }
+        Ok(Self {
+            seed,
+            players,
+            events,
+        })
+    }
+}
@@ -1,13 +1,16 @@
 use crate::{
-    action::{ATTACK_STR, AUTHORITY_STR, Action, GOLD_STR},
-    card::Card,
+    action::Action,
+    card::{Card, CardId},
     deck::Deck,
+    event::Rejected,
+    game::GameRng,
     selection::Location,
     state::State,
+    theme::{Symbols, symbols},
 };
 use std::fmt::Display;
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Hash)]
 pub struct Player {
     pub played: Deck,
     pub hand: Deck,
@@ -23,83 +26,85 @@ impl Player {
     /// Tries to buy the [Card] at a specific index in the shop for a [Player]
     /// returns Ok(())  if the player can buy it :
     ///                     - buy the card
-    ///                     - refresh the shop
-    /// returns Err(()) otherwise.
+    ///                     - refresh the shop, it shrinks once the deck is empty
+    /// returns Err(Rejected) otherwise.
     pub fn buy_from_shop(
         &mut self,
         // Complete deck of cards
         deck: &mut Deck,
         shop: &mut Deck,
         index: usize,
-    ) -> Result<(), ()> {
+        rng: &mut GameRng,
+    ) -> Result<(), Rejected> {
+        if index >= shop.len() {
+            return Err(Rejected::WrongChoice);
+        }
         let card = &shop[index];
         // If the player has enough money
         if self.gold >= card.gold {
             self.gold -= card.gold;
-            // Remove a card fr
-            if let Ok(card) = deck.remove_random() {
-                // Put the card on the discard
-                // TODO: maybe put it on the top of the deck if special ability
-                self.discard.push(std::mem::replace(&mut shop[index], card));
+            // Put the card on the discard
+            // TODO: maybe put it on the top of the deck if special ability
+            match deck.remove_random(rng) {
+                // Replace it by a card from the deck
+                Some(card) => self.discard.push(std::mem::replace(&mut shop[index], card)),
+                None => self.discard.push(shop.remove(index)),
             }
             Ok(())
         } else {
-            Err(())
+            Err(Rejected::NotEnoughGold)
         }
     }
 
     /// Tries to buy a [Card] by a [Player]
     /// - returns Ok(())  if the player can buy it : do it
-    /// - returns Err(()) otherwise.
-    pub fn buy_card(&mut self, card: Card) -> Result<(), ()> {
+    /// - returns Err(Rejected::NotEnoughGold) otherwise.
+    pub fn buy_card(&mut self, card: Card) -> Result<(), Rejected> {
         let price_card = card.gold;
         if self.gold >= price_card {
             self.gold -= price_card;
             self.discard.push(card);
             Ok(())
         } else {
-            Err(())
+            Err(Rejected::NotEnoughGold)
         }
     }
 
     /// Draw a complete hand of 5 cards for a [Player]
-    pub fn draw_hand(&mut self) {
+    pub fn draw_hand(&mut self, rng: &mut GameRng) {
         for _ in 0..5 {
-            self.draw_random_card()
+            self.draw_random_card(rng)
         }
     }
 
     /// Apply an [Action] to a [Player]
     /// changing for example the money the player has.
-    pub fn apply_action(&mut self, action: &Action, state: &mut State) {
+    pub fn apply_action(&mut self, action: &Action, state: &mut State, rng: &mut GameRng) {
         use Action::*;
         // todo handle all actions
         match action {
             Gold(i) => self.gold += i,
             Attack(i) => self.attack += i,
             Authority(i) => self.authority += i,
-            Discard(i) => (),
+            Discard(_) => (),
             Scrap { loc, nb } => *state = State::Scraping { nb: *nb, loc: *loc },
             Draw(i) => {
                 for _ in 0..*i {
-                    self.draw_random_card();
+                    self.draw_random_card(rng);
                 }
             }
             OpponentDiscard(i) => self.opponent_discard += i,
-            Complex {
-                cond: condition,
-                action: result,
-            } => (),
+            Complex { .. } => (),
             // TODO: do something change state (to copy state)
             Copy => *state = State::Copy,
-            Or(action, action1) => (), // TODO: do something
+            Or(..) => (), // TODO: do something
         }
     }
 
     /// Apply the [Action] of a [Card] to a [Player]
-    pub fn apply_card(&mut self, card: &Card, state: &mut State) {
+    pub fn apply_card(&mut self, card: &Card, state: &mut State, rng: &mut GameRng) {
         for action in card.iter() {
-            self.apply_action(action, state);
+            self.apply_action(action, state, rng);
         }
     }
 
@@ -107,7 +112,7 @@ impl Player {
     ///
     /// If the draw_pile is empty then the `discard` is put inside the
     /// `draw_pile` before drawing a new card
-    pub fn draw_random_card(&mut self) {
+    pub fn draw_random_card(&mut self, rng: &mut GameRng) {
         if self.draw_pile.is_empty() {
             for _ in 0..self.discard.len() {
                 self.draw_pile.push(self.discard.remove_last());
@@ -119,40 +124,39 @@ impl Player {
             }
         }
 
-        if let Ok(card) = self.draw_pile.remove_random() {
+        if let Some(card) = self.draw_pile.remove_random(rng) {
             self.hand.push(card)
         }
     }
 
     /// Play a [Card] of a [Player] at a specific index
-    pub fn play_card(&mut self, index: usize, state: &mut State) {
+    pub fn play_card(&mut self, index: usize, state: &mut State, rng: &mut GameRng) {
         let card = self.hand.remove(index);
-        self.apply_card(&card, state);
+        self.apply_card(&card, state, rng);
         self.played.push(card);
     }
 
-    // todo
-    pub(crate) fn activate_played_card(&mut self, index: usize, state: &mut State) {
-        ()
-    }
-
-    // todo
-    pub(crate) fn discard_card(&self, pos: &crate::selection::GamePosition) {
-        ()
-    }
-
-    // todo
-    pub(crate) fn scrap_card(&self, pos: &crate::selection::GamePosition) {
-        ()
+    /// Discard the [Card] with a given [CardId] from the `hand` of a [Player]
+    /// returns Err(Rejected::NoSuchCard) if it is not in the hand.
+    pub fn discard_card(&mut self, id: CardId) -> Result<(), Rejected> {
+        let index = self.hand.position(id).ok_or(Rejected::NoSuchCard(id))?;
+        self.discard.push(self.hand.remove(index));
+        Ok(())
     }
 }
 
 impl Display for Player {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let Symbols {
+            gold,
+            attack,
+            authority,
+            ..
+        } = symbols();
         write!(
             f,
-            "{}{}\n{}{}\n{}{}",
-            self.gold, GOLD_STR, self.attack, ATTACK_STR, self.authority, AUTHORITY_STR
+            "{}{gold}\n{}{attack}\n{}{authority}",
+            self.gold, self.attack, self.authority
         )
     }
 }
@@ -1,6 +1,7 @@
+use rand::{Rng, RngExt};
 use std::fmt::Display;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum PlayerId {
     First,
     Second,
@@ -16,8 +17,8 @@ impl PlayerId {
             panic!("Only 0 and 1 can be converted to PlayerId")
         }
     }
-    pub fn random() -> Self {
-        match rand::random_range(0..2) {
+    pub fn random<R: Rng>(rng: &mut R) -> Self {
+        match rng.random_range(0..2) {
             0 => Self::First,
             1 => Self::Second,
             _ => unreachable!(),
@@ -46,6 +47,18 @@ impl Display for PlayerId {
     }
 }
 
+impl std::str::FromStr for PlayerId {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "1st" => Ok(PlayerId::First),
+            "2nd" => Ok(PlayerId::Second),
+            _ => Err(format!("unknown player '{s}'")),
+        }
+    }
+}
+
 impl PlayerId {
     pub const fn to_usize(&self) -> usize {
         match self {
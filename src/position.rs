@@ -0,0 +1,298 @@
+//! A position of a [Game] written on a single line, like the FEN of chess
+//!
+//! The fields are separated by spaces:
+//! ```text
+//! <seed> <turn> <current player> <state> <next id> <draws> <1st player> <2nd player>
+//! <shop> <explorer> <trade deck> <scrap heap>
+//! ```
+//! - a card is the letter of its definition in [Card::collection] followed by its id,
+//!   `b40` is the Scout #40, the cards of a deck follow each other and `-` is an empty deck
+//! - a player is `<authority>:<gold>:<attack>:<opponent discards>/<hand>/<played>/<discard>/<draw pile>`
+//! - the states are `playing`, `scraping:<nb>:<hand|discard-or-hand|shop|current>`,
+//!   `discarding:<nb>:<loc>`, `copy` and `won:<1st|2nd>`
+//! - the random number generator is written as its number of draws since the seed,
+//!   the [Game] created from the code draws the same cards as the original one,
+//!   at most [MAX_DRAWS] draws are replayed
+//! - a card seen from its back is `?` without id, like in the positions sent
+//!   over the network by [Game::seen_by]
+//! - the ids of the cards are unique and below the next id
+//!
+//! The cursor, the selection and the historic are not part of the position.
+
+use crate::{
+    action::ActionLoc,
+    card::{Card, CardId},
+    deck::Deck,
+    game::{Game, GameRng},
+    player::Player,
+    state::State,
+};
+use std::{collections::HashSet, fmt::Display, str::FromStr};
+
+/// Number of draws of the random number generator accepted in a code
+///
+/// Restoring the generator replays its draws, a game draws far fewer words.
+pub const MAX_DRAWS: u64 = 1 << 20;
+
+/// Letters of the definitions, indexed like [Card::collection]
+const LETTERS: &[u8] = b"abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ";
+
+impl Game {
+    /// The position code of the [Game]
+    pub fn position_code(&self) -> impl Display {
+        PositionCode(self)
+    }
+    /// Creates a [Game] from a code written by [Game::position_code]
+    pub fn from_position_code(code: &str) -> Result<Self, String> {
+        let fields: Vec<&str> = code.split_whitespace().collect();
+        let [
+            seed,
+            turn,
+            current_player,
+            state,
+            next_id,
+            draws,
+            first,
+            second,
+            shop,
+            explorer,
+            deck,
+            scrap_heap,
+        ] = fields[..]
+        else {
+            return Err(format!(
+                "expected 12 fields in a position code, found {}",
+                fields.len()
+            ));
+        };
+        let seed = number(seed, "seed")?;
+        let mut game = Game::with_seed(seed);
+        game.turn = number(turn, "turn")?;
+        game.current_player = current_player.parse()?;
+        game.state = parse_state(state)?;
+        game.next_id = number(next_id, "next id")?;
+        let draws = number(draws, "number of draws")?;
+        if draws > MAX_DRAWS {
+            return Err(format!(
+                "{draws} draws of the generator, a code has at most {MAX_DRAWS}"
+            ));
+        }
+        game.rng = GameRng::restore(seed, draws);
+        game.players = [parse_player(first)?, parse_player(second)?];
+        game.shop = parse_deck(shop)?;
+        game.explorer = parse_deck(explorer)?;
+        game.deck = parse_deck(deck)?;
+        game.scrap_heap = parse_deck(scrap_heap)?;
+        check_ids(&game)?;
+        game.historic.clear();
+        game.log.clear();
+        game.save_turn_start();
+        Ok(game)
+    }
+}
+
+struct PositionCode<'a>(&'a Game);
+
+impl Display for PositionCode<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let game = self.0;
+        write!(f, "{} {} {} ", game.seed, game.turn, game.current_player)?;
+        match &game.state {
+            State::Playing => write!(f, "playing")?,
+            State::Scraping { nb, loc } => write!(f, "scraping:{nb}:{}", loc_code(loc))?,
+            State::Discarding { nb, loc } => write!(f, "discarding:{nb}:{}", loc_code(loc))?,
+            State::Copy => write!(f, "copy")?,
+            State::WonBy(winner) => write!(f, "won:{winner}")?,
+        }
+        write!(f, " {} {}", game.next_id, game.rng.draws())?;
+        for player in &game.players {
+            write!(
+                f,
+                " {}:{}:{}:{}/{}/{}/{}/{}",
+                player.authority,
+                player.gold,
+                player.attack,
+                player.opponent_discard,
+                Cards(&player.hand),
+                Cards(&player.played),
+                Cards(&player.discard),
+                Cards(&player.draw_pile)
+            )?;
+        }
+        write!(
+            f,
+            " {} {} {} {}",
+            Cards(&game.shop),
+            Cards(&game.explorer),
+            Cards(&game.deck),
+            Cards(&game.scrap_heap)
+        )
+    }
+}
+
+/// `b40b41a42` or `-`
+struct Cards<'a>(&'a Deck);
+
+impl Display for Cards<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.0.is_empty() {
+            return write!(f, "-");
+        }
+        let collection = Card::collection();
+        for card in self.0.iter() {
+            if card.is_hidden() {
+                write!(f, "?")?;
+                continue;
+            }
+            // Another card out of the collection is written `?`, it cannot be read back
+            let letter = collection
+                .iter()
+                .position(|def| def.def_id() == card.def_id())
+                .and_then(|index| LETTERS.get(index))
+                .unwrap_or(&b'?');
+            write!(f, "{}{}", *letter as char, card.id.0)?;
+        }
+        Ok(())
+    }
+}
+
+fn parse_deck(code: &str) -> Result<Deck, String> {
+    let mut deck = Deck::EMPTY;
+    if code == "-" {
+        return Ok(deck);
+    }
+    let collection = Card::collection();
+    let mut rest = code;
+    while let Some(letter) = rest.chars().next() {
+        if letter == '?' {
+            deck.push(Card::hidden());
+            rest = &rest[1..];
+            continue;
+        }
+        let mut card = LETTERS
+            .iter()
+            .position(|l| *l as char == letter)
+            .and_then(|index| collection.get(index))
+            .copied()
+            .ok_or_else(|| format!("'{letter}' is not a card in '{code}'"))?;
+        rest = &rest[letter.len_utf8()..];
+        let digits = rest
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(rest.len());
+        card.id = CardId(
+            rest[..digits]
+                .parse()
+                .map_err(|_| format!("missing the id of a card in '{code}'"))?,
+        );
+        deck.push(card);
+        rest = &rest[digits..];
+    }
+    Ok(deck)
+}
+
+fn parse_player(code: &str) -> Result<Player, String> {
+    let error = || format!("'{code}' is not a player like '50:0:0:0/hand/played/discard/draw'");
+    let [resources, hand, played, discard, draw_pile] = code.split('/').collect::<Vec<_>>()[..]
+    else {
+        return Err(error());
+    };
+    let resources = resources
+        .split(':')
+        .map(str::parse)
+        .collect::<Result<Vec<u32>, _>>()
+        .map_err(|_| error())?;
+    let [authority, gold, attack, opponent_discard] = resources[..] else {
+        return Err(error());
+    };
+    Ok(Player {
+        hand: parse_deck(hand)?,
+        played: parse_deck(played)?,
+        discard: parse_deck(discard)?,
+        draw_pile: parse_deck(draw_pile)?,
+        gold,
+        authority,
+        attack,
+        opponent_discard,
+    })
+}
+
+const fn loc_code(loc: &ActionLoc) -> &'static str {
+    match loc {
+        ActionLoc::Hand => "hand",
+        ActionLoc::DiscardOrHand => "discard-or-hand",
+        ActionLoc::Shop => "shop",
+        ActionLoc::CurrentCard => "current",
+    }
+}
+
+fn parse_loc(code: &str) -> Option<ActionLoc> {
+    [
+        ActionLoc::Hand,
+        ActionLoc::DiscardOrHand,
+        ActionLoc::Shop,
+        ActionLoc::CurrentCard,
+    ]
+    .into_iter()
+    .find(|loc| loc_code(loc) == code)
+}
+
+fn parse_state(code: &str) -> Result<State, String> {
+    let error = || format!("'{code}' is not a state");
+    let nb = |word: &str| word.parse().map_err(|_| error());
+    let loc = |word: &str| parse_loc(word).ok_or_else(error);
+    let state = match code.split(':').collect::<Vec<_>>()[..] {
+        ["playing"] => State::Playing,
+        ["scraping", n, l] => State::Scraping {
+            nb: nb(n)?,
+            loc: loc(l)?,
+        },
+        ["discarding", n, l] => State::Discarding {
+            nb: nb(n)?,
+            loc: loc(l)?,
+        },
+        ["copy"] => State::Copy,
+        ["won", winner] => State::WonBy(winner.parse()?),
+        _ => return Err(error()),
+    };
+    Ok(state)
+}
+
+fn number<T: FromStr>(field: &str, what: &str) -> Result<T, String> {
+    field
+        .parse()
+        .map_err(|_| format!("'{field}' is not the {what}"))
+}
+
+/// Every card has its own id, below the id of the next card entering the [Game]
+///
+/// Remark: the hidden cards have no id.
+fn check_ids(game: &Game) -> Result<(), String> {
+    let decks = game
+        .players
+        .iter()
+        .flat_map(|player| {
+            [
+                &player.hand,
+                &player.played,
+                &player.discard,
+                &player.draw_pile,
+            ]
+        })
+        .chain([&game.shop, &game.explorer, &game.deck, &game.scrap_heap]);
+    let mut ids = HashSet::new();
+    for card in decks
+        .flat_map(|deck| deck.iter())
+        .filter(|card| !card.is_hidden())
+    {
+        if !ids.insert(card.id) {
+            return Err(format!("the card {} is twice in the position", card.id));
+        }
+        if card.id.0 >= game.next_id {
+            return Err(format!(
+                "the card {} is not below the next id {}",
+                card.id, game.next_id
+            ));
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,89 @@
+use crate::{event::Event, game::Game};
+use std::{fmt::Display, str::FromStr};
+
+/// Everything needed to replay a [Game]: its seed and its [Event]s
+///
+/// A record is written with the seed on the first line
+/// and one [Event] per line:
+/// ```text
+/// seed 42
+/// play #40
+/// buy #7
+/// end
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Record {
+    pub seed: u64,
+    pub events: Vec<Event>,
+}
+
+impl Record {
+    /// Plays the events of the record on a new [Game]
+    pub fn replay(&self) -> Game {
+        self.replay_until(self.events.len())
+    }
+    /// Plays the `nb` first events of the record on a new [Game]
+    pub fn replay_until(&self, nb: usize) -> Game {
+        let mut game = Game::with_seed(self.seed);
+        for event in self.events.iter().take(nb) {
+            let _ = game.apply_event(event.clone());
+        }
+        game
+    }
+}
+
+impl Game {
+    /// Creates the [Record] of a [Game]
+    pub fn record(&self) -> Record {
+        Record {
+            seed: self.seed,
+            events: self.historic.clone(),
+        }
+    }
+}
+
+/// Writes a seed and one step per line, the format of a [Record]
+/// shared with the [fuzz cases](crate::fuzz::Case)
+pub(crate) fn write_steps<S: Display>(
+    f: &mut std::fmt::Formatter<'_>,
+    seed: u64,
+    steps: &[S],
+) -> std::fmt::Result {
+    writeln!(f, "seed {seed}")?;
+    for step in steps {
+        writeln!(f, "{step}")?;
+    }
+    Ok(())
+}
+
+/// Reads the seed and the steps written by [write_steps],
+/// the empty lines and the comments starting with `#` are skipped
+pub(crate) fn parse_steps<S: FromStr<Err = String>>(s: &str) -> Result<(u64, Vec<S>), String> {
+    let mut lines = s
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'));
+    let seed = match lines.next().and_then(|line| line.strip_prefix("seed ")) {
+        Some(seed) => seed
+            .trim()
+            .parse()
+            .map_err(|_| format!("'{seed}' is not a seed"))?,
+        None => return Err(String::from("missing the seed of the game")),
+    };
+    Ok((seed, lines.map(str::parse).collect::<Result<_, _>>()?))
+}
+
+impl Display for Record {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write_steps(f, self.seed, &self.events)
+    }
+}
+
+impl FromStr for Record {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (seed, events) = parse_steps(s)?;
+        Ok(Self { seed, events })
+    }
+}
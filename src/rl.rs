@@ -0,0 +1,309 @@
+//! A [Game] seen as a reinforcement learning environment, like a gym one
+//!
+//! The [observation] of a player is a vector of [OBSERVATION_SIZE] numbers,
+//! the moves are the [NB_ACTIONS] [Action]s of a fixed space. The copies of a card
+//! are not told apart: an [Action] plays, buys or chooses a card by its definition.
+//! ```
+//! use stars_gapa::rl::{Env, OBSERVATION_SIZE};
+//!
+//! let mut env = Env::new(100);
+//! let observation = env.reset(42);
+//! assert_eq!(observation.len(), OBSERVATION_SIZE);
+//! loop {
+//!     // A policy chooses one of the legal actions from the observation
+//!     let action = env.action_mask().iter().position(|legal| *legal).unwrap();
+//!     let step = env.step(action).unwrap();
+//!     if step.done || step.truncated {
+//!         break;
+//!     }
+//! }
+//! ```
+
+use crate::{
+    card::{Card, CardDef},
+    deck::Deck,
+    event::Event,
+    game::Game,
+    player_id::PlayerId,
+    selection::Location,
+    state::State,
+};
+use std::sync::LazyLock;
+
+/// Number of card definitions
+const DEFS: usize = Card::NB_DEFINITIONS;
+
+/// Decks counted per definition in an [observation]: the hand, played cards,
+/// discard and draw pile of the player, the shop, the played cards and discard
+/// of the opponent and the scrap heap
+const COUNTED_ZONES: usize = 8;
+/// Gold, attack and authority of both players, the number of cards in the hidden
+/// decks of the opponent, the turn and whether the player is the current one
+const SCALARS: usize = 10;
+/// One-hot [State]: playing, scraping, discarding, copying, won and lost,
+/// with the number of cards to scrap or discard
+const STATES: usize = 7;
+
+/// Size of an [observation]
+pub const OBSERVATION_SIZE: usize = COUNTED_ZONES * DEFS + SCALARS + STATES;
+
+/// A move of the fixed action space, the cards are named by the index
+/// of their definition in [Card::collection]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+    NextTurn,
+    Attack,
+    BuyExplorer,
+    PlayAll,
+    /// Choose no card
+    ChooseNone,
+    Buy(usize),
+    Play(usize),
+    /// Choose a card of the hand
+    ChooseHand(usize),
+    /// Choose a card of the discard pile
+    ChooseDiscard(usize),
+    /// Choose a card of the shop
+    ChooseShop(usize),
+    /// Choose a played card
+    ChoosePlayed(usize),
+}
+
+/// Size of the action space
+pub const NB_ACTIONS: usize = 5 + 6 * DEFS;
+
+/// The definitions of [Card::collection], compared by address
+static DEFINITIONS: LazyLock<[&'static CardDef; DEFS]> =
+    LazyLock::new(|| Card::collection().map(|card| card.def()));
+
+/// Index of the definition of a [Card] in [Card::collection]
+pub fn def_index(card: &Card) -> Option<usize> {
+    DEFINITIONS
+        .iter()
+        .position(|def| std::ptr::eq(*def, card.def()))
+}
+
+impl Action {
+    /// The action at an index of the action space
+    pub fn from_index(index: usize) -> Option<Self> {
+        use Action::*;
+        let action = match index {
+            0 => NextTurn,
+            1 => Attack,
+            2 => BuyExplorer,
+            3 => PlayAll,
+            4 => ChooseNone,
+            _ if index < NB_ACTIONS => {
+                let def = (index - 5) % DEFS;
+                match (index - 5) / DEFS {
+                    0 => Buy(def),
+                    1 => Play(def),
+                    2 => ChooseHand(def),
+                    3 => ChooseDiscard(def),
+                    4 => ChooseShop(def),
+                    _ => ChoosePlayed(def),
+                }
+            }
+            _ => return None,
+        };
+        Some(action)
+    }
+    /// The index of the action in the action space
+    pub fn index(&self) -> usize {
+        use Action::*;
+        match *self {
+            NextTurn => 0,
+            Attack => 1,
+            BuyExplorer => 2,
+            PlayAll => 3,
+            ChooseNone => 4,
+            Buy(def) => 5 + def,
+            Play(def) => 5 + DEFS + def,
+            ChooseHand(def) => 5 + 2 * DEFS + def,
+            ChooseDiscard(def) => 5 + 3 * DEFS + def,
+            ChooseShop(def) => 5 + 4 * DEFS + def,
+            ChoosePlayed(def) => 5 + 5 * DEFS + def,
+        }
+    }
+    /// The action of an [Event] of the current player,
+    /// `None` for the events outside of the action space
+    pub fn from_event(game: &Game, event: &Event) -> Option<Self> {
+        let def = |loc: Location, id| {
+            let position = game.position_of(id)?;
+            (position.loc == loc)
+                .then(|| game.card_at(&position).and_then(def_index))
+                .flatten()
+        };
+        let action = match event {
+            Event::NextTurn => Action::NextTurn,
+            Event::Attack => Action::Attack,
+            Event::BuyExplorer => Action::BuyExplorer,
+            Event::PlayAll => Action::PlayAll,
+            Event::Buy(id) => Action::Buy(def(Location::Shop, *id)?),
+            Event::Play(id) => Action::Play(def(Location::Hand, *id)?),
+            Event::Choose(ids) => match ids[..] {
+                [] => Action::ChooseNone,
+                [id] => {
+                    let position = game.position_of(id)?;
+                    let def = def_index(game.card_at(&position)?)?;
+                    match position.loc {
+                        Location::Hand => Action::ChooseHand(def),
+                        Location::Discard => Action::ChooseDiscard(def),
+                        Location::Shop => Action::ChooseShop(def),
+                        Location::Played => Action::ChoosePlayed(def),
+                        _ => return None,
+                    }
+                }
+                _ => return None,
+            },
+            Event::Activate(_) => return None,
+        };
+        Some(action)
+    }
+    /// The [Event] of the action for the current player,
+    /// `None` if there is no card of the definition where it is looked for
+    pub fn event(&self, game: &Game) -> Option<Event> {
+        use Action::*;
+        let player = &game[game.current_player];
+        let find = |deck: &Deck, def: usize| {
+            deck.iter()
+                .find(|card| def_index(card) == Some(def))
+                .map(|card| card.id)
+        };
+        let event = match *self {
+            NextTurn => Event::NextTurn,
+            Attack => Event::Attack,
+            BuyExplorer => Event::BuyExplorer,
+            PlayAll => Event::PlayAll,
+            ChooseNone => Event::Choose(vec![]),
+            Buy(def) => Event::Buy(find(&game.shop, def)?),
+            Play(def) => Event::Play(find(&player.hand, def)?),
+            ChooseHand(def) => Event::Choose(vec![find(&player.hand, def)?]),
+            ChooseDiscard(def) => Event::Choose(vec![find(&player.discard, def)?]),
+            ChooseShop(def) => Event::Choose(vec![find(&game.shop, def)?]),
+            ChoosePlayed(def) => Event::Choose(vec![find(&player.played, def)?]),
+        };
+        Some(event)
+    }
+}
+
+/// The [Game] seen by a `player`, the cards hidden to it are only counted
+pub fn observation(game: &Game, player: PlayerId) -> [f32; OBSERVATION_SIZE] {
+    let mut observation = [0.; OBSERVATION_SIZE];
+    let (me, opponent) = (&game[player], &game[player.other()]);
+    let zones = [
+        &me.hand,
+        &me.played,
+        &me.discard,
+        &me.draw_pile,
+        &game.shop,
+        &opponent.played,
+        &opponent.discard,
+        &game.scrap_heap,
+    ];
+    for (zone, deck) in zones.into_iter().enumerate() {
+        for def in deck.iter().filter_map(def_index) {
+            observation[zone * DEFS + def] += 1.;
+        }
+    }
+    let scalars = [
+        me.gold,
+        me.attack,
+        me.authority,
+        opponent.gold,
+        opponent.attack,
+        opponent.authority,
+        opponent.hand.len() as u32,
+        opponent.draw_pile.len() as u32,
+        game.turn,
+        (game.current_player == player) as u32,
+    ];
+    let start = COUNTED_ZONES * DEFS;
+    for (i, scalar) in scalars.into_iter().enumerate() {
+        observation[start + i] = scalar as f32;
+    }
+    let start = start + SCALARS;
+    let (state, nb) = match game.state {
+        State::Playing => (0, 0),
+        State::Scraping { nb, .. } => (1, nb),
+        State::Discarding { nb, .. } => (2, nb),
+        State::Copy => (3, 1),
+        State::WonBy(winner) if winner == player => (4, 0),
+        State::WonBy(_) => (5, 0),
+    };
+    observation[start + state] = 1.;
+    observation[start + STATES - 1] = nb as f32;
+    observation
+}
+
+/// What a [step](Env::step) gives back
+#[derive(Debug, Clone, PartialEq)]
+pub struct Step {
+    /// The [observation] of the player to play next
+    pub observation: [f32; OBSERVATION_SIZE],
+    /// Reward of each player, indexed by [PlayerId]:
+    /// 1 for the winner and -1 for the loser at the end of the [Game]
+    pub rewards: [f32; 2],
+    /// The [Game] is won
+    pub done: bool,
+    /// The [Game] has been stopped after the maximum number of turns
+    pub truncated: bool,
+}
+
+/// A [Game] played through the fixed action space
+#[derive(Debug, Clone)]
+pub struct Env {
+    pub game: Game,
+    /// Turns played before a [Game] is truncated
+    pub max_turns: u32,
+}
+
+impl Env {
+    pub fn new(max_turns: u32) -> Self {
+        Self {
+            game: Game::with_seed(0),
+            max_turns,
+        }
+    }
+    /// Starts a new [Game] from a `seed`,
+    /// returns the [observation] of its first player
+    pub fn reset(&mut self, seed: u64) -> [f32; OBSERVATION_SIZE] {
+        self.game = Game::with_seed(seed);
+        observation(&self.game, self.game.current_player)
+    }
+    /// The legal actions of the current player, indexed by [Action::index]
+    pub fn action_mask(&self) -> [bool; NB_ACTIONS] {
+        let mut mask = [false; NB_ACTIONS];
+        for event in self.game.legal_events() {
+            if let Some(action) = Action::from_event(&self.game, &event) {
+                mask[action.index()] = true;
+            }
+        }
+        mask
+    }
+    /// Plays the action at an `index` of the action space for the current player
+    ///
+    /// Returns None if the action is not legal, the [Game] is not changed.
+    pub fn step(&mut self, index: usize) -> Option<Step> {
+        if !self.action_mask().get(index).copied().unwrap_or(false) {
+            return None;
+        }
+        let event = Action::from_index(index).and_then(|action| action.event(&self.game))?;
+        self.game.apply_event(event).ok()?;
+        let mut rewards = [0.; 2];
+        let done = match self.game.state {
+            State::WonBy(winner) => {
+                rewards[winner] = 1.;
+                rewards[winner.other()] = -1.;
+                true
+            }
+            _ => false,
+        };
+        Some(Step {
+            observation: observation(&self.game, self.game.current_player),
+            rewards,
+            done,
+            truncated: !done && self.game.turn > self.max_turns,
+        })
+    }
+}
@@ -1,4 +1,5 @@
-use crate::{deck::Deck, game::Game, player_id::PlayerId};
+use crate::player_id::PlayerId;
+use rand::{Rng, RngExt};
 use std::fmt::Display;
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
@@ -25,6 +26,22 @@ impl Display for Location {
     }
 }
 
+impl std::str::FromStr for Location {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "explorer" => Ok(Explorer),
+            "shop" => Ok(Shop),
+            "hand" => Ok(Hand),
+            "played" => Ok(Played),
+            "discard" => Ok(Discard),
+            "draw" => Ok(DrawPile),
+            _ => Err(format!("unknown location '{s}'")),
+        }
+    }
+}
+
 #[derive(PartialEq, Eq)]
 enum TopOrBot {
     Top,
@@ -47,6 +64,21 @@ impl TopOrBot {
     }
 }
 impl Location {
+    /// Single word naming a [Location], parsed back by [std::str::FromStr]
+    pub const fn code(&self) -> &'static str {
+        match self {
+            Explorer => "explorer",
+            Shop => "shop",
+            Hand => "hand",
+            Played => "played",
+            Discard => "discard",
+            DrawPile => "draw",
+        }
+    }
+    /// The decks shown face down, their cards are seen in a popup
+    pub const fn is_hidden(&self) -> bool {
+        matches!(self, Discard | DrawPile)
+    }
     pub const fn next_right(&self) -> Location {
         match self {
             Explorer => Shop,
@@ -105,8 +137,8 @@ impl Location {
         let (loc, new_pos) = self.next_up_position(&pos);
         (loc, new_pos.to_player(current_player))
     }
-    pub fn random() -> Self {
-        match rand::random_range(0..6) {
+    pub fn random<R: Rng>(rng: &mut R) -> Self {
+        match rng.random_range(0..6) {
             0 => Explorer,
             1 => Shop,
             2 => Hand,
@@ -160,6 +192,28 @@ impl Display for GamePosition {
     }
 }
 
+/// Parses a [GamePosition] written as `player:location:index`
+/// for example `1st:hand:0` or `2nd:discard:3`
+impl std::str::FromStr for GamePosition {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = s.split(':');
+        let (Some(player), Some(loc), Some(index), None) =
+            (parts.next(), parts.next(), parts.next(), parts.next())
+        else {
+            return Err(format!("'{s}' is not a position 'player:location:index'"));
+        };
+        Ok(Self {
+            player: player.parse()?,
+            loc: loc.parse()?,
+            index: index
+                .parse()
+                .map_err(|_| format!("'{index}' is not an index"))?,
+        })
+    }
+}
+
 impl Default for GamePosition {
     fn default() -> Self {
         Self {
@@ -204,11 +258,11 @@ impl GamePosition {
             index: 0,
         }
     }
-    pub fn random() -> Self {
+    pub fn random<R: Rng>(rng: &mut R) -> Self {
         Self {
-            player: PlayerId::random(),
-            loc: Location::random(),
-            index: rand::random_range(0..10),
+            player: PlayerId::random(rng),
+            loc: Location::random(rng),
+            index: rng.random_range(0..10),
         }
     }
 }
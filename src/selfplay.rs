@@ -0,0 +1,197 @@
+//! Seeded games played by computer [Agent]s in parallel threads,
+//! every decision written as a line of JSON for offline training and analysis
+//!
+//! ```text
+//! {"seed":3,"turn":1,"player":"1st","observation":[0,2,...],"event":"play all","action":3,"outcome":-1}
+//! ```
+//! The `observation` is the [rl::observation] of the player, the `action` the index
+//! of the event in the [rl] action space or `null`, and the `outcome` is 1 when the player
+//! won the game, -1 when it lost and 0 when the game was stopped after the maximum of turns.
+
+use crate::{
+    ai,
+    event::Event,
+    game::{Game, GameRng},
+    player_id::PlayerId,
+    rl::{self, Action, OBSERVATION_SIZE},
+    state::State,
+};
+use rand::{RngExt, SeedableRng};
+use std::{
+    collections::BTreeMap,
+    fmt::Display,
+    io::Write,
+    str::FromStr,
+    sync::{Mutex, mpsc::channel},
+};
+
+/// A computer player of self-play games
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Agent {
+    /// The greedy computer player of [ai]
+    Greedy,
+    /// A player choosing uniformly among the legal events
+    Random,
+}
+
+impl FromStr for Agent {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "greedy" => Ok(Agent::Greedy),
+            "random" => Ok(Agent::Random),
+            _ => Err(format!(
+                "unknown agent '{s}', expected 'greedy' or 'random'"
+            )),
+        }
+    }
+}
+
+impl Agent {
+    fn choose(&self, game: &Game, rng: &mut GameRng) -> Event {
+        match self {
+            Agent::Greedy => ai::choose_event(game),
+            Agent::Random => {
+                let mut legal = game.legal_events();
+                match legal.len() {
+                    0 => Event::NextTurn,
+                    len => legal.swap_remove(rng.random_range(0..len)),
+                }
+            }
+        }
+    }
+}
+
+/// An [Event] chosen by a player, with the [Game] as it saw it
+#[derive(Debug, Clone, PartialEq)]
+pub struct Decision {
+    pub seed: u64,
+    pub turn: u32,
+    pub player: PlayerId,
+    pub observation: [f32; OBSERVATION_SIZE],
+    pub event: Event,
+    /// Index of the event in the [rl] action space
+    pub action: Option<usize>,
+    /// 1 when the player won the game, -1 when it lost, 0 for an unfinished game
+    pub outcome: i8,
+}
+
+impl Display for Decision {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            r#"{{"seed":{},"turn":{},"player":"{}","observation":["#,
+            self.seed, self.turn, self.player
+        )?;
+        for (i, value) in self.observation.iter().enumerate() {
+            if i != 0 {
+                write!(f, ",")?;
+            }
+            write!(f, "{value}")?;
+        }
+        write!(f, r#"],"event":"{}","action":"#, self.event)?;
+        match self.action {
+            Some(action) => write!(f, "{action}")?,
+            None => write!(f, "null")?,
+        }
+        write!(f, r#","outcome":{}}}"#, self.outcome)
+    }
+}
+
+/// Seed of the random choices of the [Agent]s in the [Game] of a `seed`
+///
+/// The seed is mixed by a step of SplitMix64: with the seed of the [Game]
+/// the choices would follow its shuffles and bias the decisions.
+pub fn agent_seed(seed: u64) -> u64 {
+    let mut z = seed.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+/// Plays the [Game] of a `seed` between two [Agent]s until it is won
+/// or `max_turns` are played, returns every decision of the players
+pub fn play(seed: u64, agents: [Agent; 2], max_turns: u32) -> Vec<Decision> {
+    let mut game = Game::with_seed(seed);
+    let mut rng = GameRng::seed_from_u64(agent_seed(seed));
+    let mut decisions = Vec::new();
+    while !matches!(game.state, State::WonBy(_)) && game.turn <= max_turns {
+        let (player, turn) = (game.current_player, game.turn);
+        let observation = rl::observation(&game, player);
+        let chosen = agents[player].choose(&game, &mut rng);
+        // The greedy player never gets stuck on a rejected event, the turn ends instead,
+        // the action is the one of the event actually applied
+        let applied = [chosen, Event::NextTurn].into_iter().find_map(|event| {
+            let action = Action::from_event(&game, &event).map(|action| action.index());
+            game.apply_event(event.clone())
+                .ok()
+                .map(|()| (event, action))
+        });
+        let Some((event, action)) = applied else {
+            break;
+        };
+        decisions.push(Decision {
+            seed,
+            turn,
+            player,
+            observation,
+            event,
+            action,
+            outcome: 0,
+        });
+    }
+    if let State::WonBy(winner) = game.state {
+        for decision in &mut decisions {
+            decision.outcome = if decision.player == winner { 1 } else { -1 };
+        }
+    }
+    decisions
+}
+
+/// Plays `nb_games` games with the seeds `first_seed`, `first_seed + 1`...
+/// in `nb_threads` threads and writes their decisions to `output`
+///
+/// The games are written in the order of their seeds, the output only depends
+/// on the arguments and not on the threads. Returns the number of decisions.
+pub fn generate(
+    first_seed: u64,
+    nb_games: u64,
+    nb_threads: usize,
+    agents: [Agent; 2],
+    max_turns: u32,
+    mut output: impl Write,
+) -> std::io::Result<usize> {
+    let seeds = Mutex::new(0..nb_games);
+    let (sender, games) = channel();
+    std::thread::scope(|scope| {
+        for _ in 0..nb_threads.max(1) {
+            let sender = sender.clone();
+            let seeds = &seeds;
+            scope.spawn(move || {
+                while let Some(i) = seeds.lock().unwrap().next() {
+                    let seed = first_seed.wrapping_add(i);
+                    if sender.send((i, play(seed, agents, max_turns))).is_err() {
+                        break;
+                    }
+                }
+            });
+        }
+        drop(sender);
+        let mut waiting = BTreeMap::new();
+        let mut next = 0;
+        let mut nb_decisions = 0;
+        for (i, decisions) in games {
+            waiting.insert(i, decisions);
+            while let Some(decisions) = waiting.remove(&next) {
+                for decision in &decisions {
+                    writeln!(output, "{decision}")?;
+                }
+                nb_decisions += decisions.len();
+                next += 1;
+            }
+        }
+        output.flush()?;
+        Ok(nb_decisions)
+    })
+}
@@ -1,11 +1,17 @@
-use crate::{action::ActionLoc, player_id::PlayerId, selection::Location};
+use crate::{action::ActionLoc, player_id::PlayerId};
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Hash)]
 pub enum State {
     Playing,
-    Scraping { nb: u32, loc: ActionLoc },
-    Discarding { nb: u32, loc: ActionLoc },
-    // TODO: do something with this state
+    Scraping {
+        nb: u32,
+        loc: ActionLoc,
+    },
+    Discarding {
+        nb: u32,
+        loc: ActionLoc,
+    },
+    /// The game is over, the game over screen of the [App](crate::app::App) is shown
     WonBy(PlayerId),
     Copy,
 }
@@ -0,0 +1,185 @@
+use crate::action::{
+    ATTACK_STR, AUTHORITY_STR, BASE_STR, DISCARD_STR, DRAW_STR, GOLD_STR, OUTPOST_STR, SCRAP_STR,
+};
+#[cfg(feature = "tui")]
+use crate::faction::Faction;
+#[cfg(feature = "tui")]
+use ratatui::style::Color;
+use std::{
+    fmt::Display,
+    str::FromStr,
+    sync::atomic::{AtomicU8, Ordering},
+};
+
+/// Symbols used to display the actions, the factions and the defenses
+///
+/// The emoji do not have the same width in every terminal,
+/// the ASCII theme always keeps the cards aligned.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Theme {
+    #[default]
+    Emoji,
+    Ascii,
+}
+
+/// Symbols of a [Theme]
+#[derive(Debug)]
+pub struct Symbols {
+    pub base: &'static str,
+    pub outpost: &'static str,
+    pub gold: &'static str,
+    pub attack: &'static str,
+    pub authority: &'static str,
+    pub scrap: &'static str,
+    pub discard: &'static str,
+    pub draw: &'static str,
+    /// Indexed by [Faction::to_usize](crate::faction::Faction::to_usize)
+    pub factions: [&'static str; 4],
+}
+
+pub const EMOJI: Symbols = Symbols {
+    base: BASE_STR,
+    outpost: OUTPOST_STR,
+    gold: GOLD_STR,
+    attack: ATTACK_STR,
+    authority: AUTHORITY_STR,
+    scrap: SCRAP_STR,
+    discard: DISCARD_STR,
+    draw: DRAW_STR,
+    factions: ["🐉", "🏦", "🌟", "⚙️"],
+};
+
+pub const ASCII: Symbols = Symbols {
+    base: "B",
+    outpost: "O",
+    gold: "$",
+    attack: "*",
+    authority: "+",
+    scrap: "X",
+    discard: "~",
+    draw: "d",
+    factions: ["b", "t", "s", "m"],
+};
+
+/// [Theme] used by every [Display] of the game
+static THEME: AtomicU8 = AtomicU8::new(0);
+
+impl Theme {
+    pub const ALL: [Theme; 2] = [Theme::Emoji, Theme::Ascii];
+
+    /// The [Theme] used to display the game
+    pub fn current() -> Self {
+        match THEME.load(Ordering::Relaxed) {
+            0 => Theme::Emoji,
+            _ => Theme::Ascii,
+        }
+    }
+    /// Displays the game with this [Theme] from now on
+    pub fn set(self) {
+        THEME.store(self as u8, Ordering::Relaxed);
+    }
+    /// The [Theme] after this one in [Theme::ALL]
+    pub fn next(self) -> Self {
+        Self::ALL[(self as usize + 1) % Self::ALL.len()]
+    }
+    pub const fn symbols(self) -> &'static Symbols {
+        match self {
+            Theme::Emoji => &EMOJI,
+            Theme::Ascii => &ASCII,
+        }
+    }
+}
+
+/// Symbols of the current [Theme]
+pub fn symbols() -> &'static Symbols {
+    Theme::current().symbols()
+}
+
+impl Display for Theme {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Theme::Emoji => write!(f, "emoji"),
+            Theme::Ascii => write!(f, "ascii"),
+        }
+    }
+}
+
+impl FromStr for Theme {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim() {
+            "emoji" => Ok(Theme::Emoji),
+            "ascii" => Ok(Theme::Ascii),
+            _ => Err(format!("unknown theme '{s}', expected 'emoji' or 'ascii'")),
+        }
+    }
+}
+
+/// Colours of the factions
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Palette {
+    #[default]
+    Normal,
+    /// Okabe-Ito colours, told apart with any colour vision deficiency
+    ColourBlind,
+}
+
+/// [Palette] used by every widget of the game
+static PALETTE: AtomicU8 = AtomicU8::new(0);
+
+impl Palette {
+    pub const ALL: [Palette; 2] = [Palette::Normal, Palette::ColourBlind];
+
+    /// The [Palette] used to display the game
+    pub fn current() -> Self {
+        match PALETTE.load(Ordering::Relaxed) {
+            0 => Palette::Normal,
+            _ => Palette::ColourBlind,
+        }
+    }
+    /// Displays the game with this [Palette] from now on
+    pub fn set(self) {
+        PALETTE.store(self as u8, Ordering::Relaxed);
+    }
+    /// The [Palette] after this one in [Palette::ALL]
+    pub fn next(self) -> Self {
+        Self::ALL[(self as usize + 1) % Self::ALL.len()]
+    }
+    #[cfg(feature = "tui")]
+    pub const fn faction(self, faction: &Faction) -> Color {
+        match (self, faction) {
+            (Palette::Normal, Faction::Blob) => Color::Green,
+            (Palette::Normal, Faction::Trade) => Color::Blue,
+            (Palette::Normal, Faction::Star) => Color::Yellow,
+            (Palette::Normal, Faction::Machine) => Color::Red,
+            (Palette::ColourBlind, Faction::Blob) => Color::Rgb(0, 158, 115),
+            (Palette::ColourBlind, Faction::Trade) => Color::Rgb(86, 180, 233),
+            (Palette::ColourBlind, Faction::Star) => Color::Rgb(240, 228, 66),
+            (Palette::ColourBlind, Faction::Machine) => Color::Rgb(213, 94, 0),
+        }
+    }
+}
+
+impl Display for Palette {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Palette::Normal => write!(f, "normal"),
+            Palette::ColourBlind => write!(f, "colour-blind"),
+        }
+    }
+}
+
+impl FromStr for Palette {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim() {
+            "normal" => Ok(Palette::Normal),
+            "colour-blind" | "color-blind" => Ok(Palette::ColourBlind),
+            _ => Err(format!(
+                "unknown palette '{s}', expected 'normal' or 'colour-blind'"
+            )),
+        }
+    }
+}
@@ -0,0 +1,11 @@
+//! Rendering of a [Game](crate::game::Game) in a terminal with ratatui
+//!
+//! The rules engine does not depend on this module,
+//! it is only built with the `tui` feature.
+
+pub mod card;
+pub mod deck;
+pub mod game;
+pub mod hot_seat;
+pub mod log;
+pub mod view;
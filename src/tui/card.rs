@@ -0,0 +1,257 @@
+use crate::{
+    action::Action,
+    card::{Card, Life},
+    deck::Deck,
+    faction::Faction,
+    theme::symbols,
+};
+use ratatui::{
+    layout::Constraint,
+    prelude::{Buffer, Rect},
+    style::Style,
+    text::{Line, Span},
+    widgets::{Block, BorderType, Clear, Paragraph, Widget, Wrap},
+};
+
+impl Card {
+    /// Creates a [CardWidget] from a [Card]
+    /// Remark: [CardWidget] implements the [Widget] trait and can be displayed
+    pub const fn widget(&self) -> CardWidget<'_> {
+        CardWidget {
+            card: self,
+            selected: false,
+            hovered: false,
+            hinted: false,
+            dimmed: false,
+            played: None,
+            full_text: false,
+        }
+    }
+    /// Creates a [CardZoomWidget] describing every detail of a [Card]
+    pub const fn zoom_widget(&self) -> CardZoomWidget<'_> {
+        CardZoomWidget { card: self }
+    }
+}
+
+#[derive(Debug)]
+pub struct CardWidget<'a> {
+    selected: bool,
+    /// The mouse is over the card
+    hovered: bool,
+    /// The card of the suggested move
+    hinted: bool,
+    /// The card is unaffordable
+    dimmed: bool,
+    /// Cards played this turn, the abilities they trigger are highlighted
+    played: Option<&'a Deck>,
+    /// The actions are described with sentences
+    full_text: bool,
+    card: &'a Card,
+}
+
+impl<'a> CardWidget<'a> {
+    pub fn selected(self) -> Self {
+        Self {
+            selected: true,
+            ..self
+        }
+    }
+    pub fn set_selection(self, selected: bool) -> Self {
+        Self { selected, ..self }
+    }
+    pub fn set_hover(self, hovered: bool) -> Self {
+        Self { hovered, ..self }
+    }
+    pub fn set_hint(self, hinted: bool) -> Self {
+        Self { hinted, ..self }
+    }
+    pub fn set_dimmed(self, dimmed: bool) -> Self {
+        Self { dimmed, ..self }
+    }
+    pub fn set_played(self, played: Option<&'a Deck>) -> Self {
+        Self { played, ..self }
+    }
+    pub fn set_full_text(self, full_text: bool) -> Self {
+        Self { full_text, ..self }
+    }
+    /// Style of an [Action] of the card, the ally abilities
+    /// that will be triggered are highlighted
+    fn action_style(&self, action: &Action) -> Style {
+        match (action, self.played) {
+            (Action::Complex { cond, .. }, Some(played))
+                if cond.is_satisfied(self.card, played) =>
+            {
+                let color = self.card.faction.first().map(Faction::color);
+                Style::new().bold().fg(color.unwrap_or_default())
+            }
+            _ => Style::new(),
+        }
+    }
+    fn style(&self) -> Style {
+        if self.dimmed {
+            Style::new().dim()
+        } else {
+            Style::new()
+        }
+    }
+    /// Renders the card on a single line of a list:
+    /// the faction, the cost, the name and the actions
+    fn render_line(self, area: Rect, buf: &mut Buffer) {
+        let card = self.card;
+        let mut spans = vec![];
+        if self.hinted {
+            spans.push(Span::styled("» ", Style::new().bold()));
+        }
+        if let Some(faction) = card.faction.first() {
+            spans.push(Span::styled(
+                format!("{} ", card.faction),
+                Style::new().fg(faction.color()),
+            ));
+        }
+        spans.push(Span::raw(format!(
+            "{} {} ",
+            Action::Gold(card.gold),
+            card.name()
+        )));
+        for action in card.iter() {
+            spans.push(Span::styled(
+                format!("{} ", format!("{action}").trim_end()),
+                self.action_style(action),
+            ));
+        }
+        let style = if self.selected {
+            self.style().reversed()
+        } else if self.hovered {
+            self.style().underlined()
+        } else {
+            self.style()
+        };
+        Line::from(spans).style(style).render(area, buf);
+    }
+}
+
+impl<'a> Widget for CardWidget<'a> {
+    fn render(self, area: Rect, buf: &mut Buffer)
+    where
+        Self: Sized,
+    {
+        // Too small for the borders, the card is a line of a list
+        if area.height < 3 {
+            return self.render_line(area, buf);
+        }
+        let card = self.card;
+        let gold_string = if card.gold == 0 {
+            String::from("")
+        } else {
+            format!("{}", Action::Gold(card.gold))
+        };
+        let life_string = match card.life {
+            Some(Life::Base(i)) => format!("{i}{}", symbols().base),
+            Some(Life::OutPost(i)) => format!("{i}{}", symbols().outpost),
+            None => String::new(),
+        };
+        let name_string =
+            if card.name().len() + life_string.chars().count() + 2 >= area.width as usize {
+                format!("{}", card.initials())
+            } else {
+                String::from(card.name())
+            };
+        let color = card.faction.first().map(Faction::color);
+        let lines: Vec<Line> = card
+            .iter()
+            .map(|action| {
+                let text = if self.full_text {
+                    format!("{}", action.sentence())
+                } else {
+                    format!("{action}").trim_end().to_string()
+                };
+                Line::from(text).style(self.action_style(action))
+            })
+            .collect();
+        // The selection and the hover keep their shape in every palette
+        let border_style = if self.selected {
+            Style::new().bold()
+        } else {
+            Style::new()
+        };
+        let paragraph = if self.full_text {
+            Paragraph::new(lines).wrap(Wrap { trim: true })
+        } else {
+            Paragraph::new(lines)
+        };
+        paragraph
+            .centered()
+            .style(self.style())
+            .block(
+                Block::bordered()
+                    .border_type(if self.selected {
+                        BorderType::Double
+                    } else if self.hovered {
+                        BorderType::Thick
+                    } else {
+                        BorderType::Rounded
+                    })
+                    .title_top(Line::from(gold_string).right_aligned())
+                    .title_bottom(life_string)
+                    .title_bottom(Line::from(name_string).right_aligned())
+                    .title_top(Line::from(format!("{}", card.faction)).left_aligned())
+                    .title_top(if self.hinted {
+                        Line::styled("hint", Style::new().bold()).centered()
+                    } else {
+                        Line::default()
+                    })
+                    .border_style(match color {
+                        Some(color) => border_style.fg(color),
+                        None => border_style,
+                    }),
+            )
+            .render(area, buf);
+    }
+}
+
+/// Popup with the full name, the cost, the factions, the defense
+/// and a sentence for every [Action] of a [Card]
+#[derive(Debug)]
+pub struct CardZoomWidget<'a> {
+    card: &'a Card,
+}
+
+impl Widget for CardZoomWidget<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer)
+    where
+        Self: Sized,
+    {
+        let card = self.card;
+        let mut lines = vec![];
+        lines.push(Line::from(format!("Cost: {} gold", card.gold)));
+        let factions: Vec<&str> = card.faction.iter().map(Faction::name).collect();
+        lines.push(Line::from(if factions.is_empty() {
+            String::from("No faction")
+        } else {
+            format!("Faction: {}", factions.join(", "))
+        }));
+        lines.push(Line::from(match card.life {
+            Some(Life::Base(i)) => format!("Base with {i} defense"),
+            Some(Life::OutPost(i)) => format!("Outpost with {i} defense"),
+            None => String::from("Ship"),
+        }));
+        lines.push(Line::from(""));
+        for action in card.iter() {
+            lines.push(Line::from(format!("- {}", action.sentence())));
+        }
+
+        let area = area.centered(
+            Constraint::Length(area.width.min(60)),
+            Constraint::Length(lines.len() as u16 + 4),
+        );
+        Clear.render(area, buf);
+        Paragraph::new(lines)
+            .wrap(Wrap { trim: false })
+            .block(
+                Block::bordered()
+                    .title_top(Line::from(card.name()).centered())
+                    .border_style(Style::new().yellow()),
+            )
+            .render(area, buf);
+    }
+}
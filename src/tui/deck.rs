@@ -0,0 +1,293 @@
+use crate::{
+    deck::Deck,
+    player_id::PlayerId,
+    selection::{GamePosition, Location},
+    tui::{game::LayoutMode, view::View},
+};
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Margin},
+    prelude::{Buffer, Rect},
+    style::Style,
+    text::Line,
+    widgets::{Block, BorderType, Clear, Paragraph, Widget},
+};
+
+impl Deck {
+    pub fn widget<'a, 'b>(&'a self) -> DeckWidget<'a, 'b> {
+        DeckWidget {
+            name: "",
+            hidden: false,
+            face_down: false,
+            popup_area: None,
+            scroll: 0,
+            hover: None,
+            hint: None,
+            deck: self,
+            selection: vec![],
+            max_cols: None,
+            budget: None,
+            played: None,
+            list: false,
+            full_text: false,
+        }
+    }
+}
+
+pub struct DeckWidget<'a, 'b> {
+    name: &'b str,
+    selection: Vec<usize>,
+    hidden: bool,
+    /// Only the number of cards is displayed, even when selected,
+    /// like for a [Deck] with cards seen from their back
+    face_down: bool,
+    /// Area covered by the popup of a hidden deck, the whole buffer by default
+    popup_area: Option<Rect>,
+    /// Rows of cards hidden above the popup
+    scroll: usize,
+    /// Index of the card under the mouse
+    hover: Option<usize>,
+    /// Index of the card of the suggested move
+    hint: Option<usize>,
+    max_cols: Option<usize>,
+    /// Gold of the buyer, the cards costing more are dimmed
+    budget: Option<u32>,
+    /// Cards played this turn, the ally abilities they trigger are highlighted
+    played: Option<&'a Deck>,
+    /// Each card is a line of a list, for the small terminals
+    list: bool,
+    /// The actions are described with sentences, for the large terminals
+    full_text: bool,
+    deck: &'a Deck,
+}
+
+impl<'a, 'b> DeckWidget<'a, 'b> {
+    pub fn hidden(self) -> Self {
+        Self {
+            hidden: true,
+            ..self
+        }
+    }
+    pub fn face_down(self) -> Self {
+        Self {
+            face_down: true,
+            ..self
+        }
+    }
+    pub fn set_name<'c>(self, name: &'c str) -> DeckWidget<'a, 'c> {
+        DeckWidget { name, ..self }
+    }
+    pub fn set_popup_area(self, popup_area: Rect) -> Self {
+        Self {
+            popup_area: Some(popup_area),
+            ..self
+        }
+    }
+    pub fn set_max_cols(self, max_cols: usize) -> DeckWidget<'a, 'b> {
+        DeckWidget {
+            max_cols: Some(max_cols),
+            ..self
+        }
+    }
+    /// Adapts the cards to the size of the terminal
+    pub fn set_layout(self, mode: LayoutMode) -> Self {
+        Self {
+            list: mode == LayoutMode::Compact,
+            full_text: mode == LayoutMode::Wide,
+            ..self
+        }
+    }
+    pub fn set_budget(self, budget: u32) -> Self {
+        Self {
+            budget: Some(budget),
+            ..self
+        }
+    }
+    pub fn set_played(self, played: &'a Deck) -> Self {
+        Self {
+            played: Some(played),
+            ..self
+        }
+    }
+    pub fn set_selection(mut self, view: &View, loc: Location, playerid: PlayerId) -> Self {
+        use Location::*;
+        pub fn get_index(pos: &GamePosition, loc: Location, playerid: PlayerId) -> Option<usize> {
+            if (pos.player == playerid || pos.loc == Shop || pos.loc == Explorer) && pos.loc == loc
+            {
+                Some(pos.index)
+            } else {
+                None
+            }
+        }
+        if let Some(i) = get_index(&view.position, loc, playerid) {
+            self.selection.push(i)
+        }
+        self.scroll = view.scroll;
+        self.hover = view
+            .hover
+            .as_ref()
+            .and_then(|pos| get_index(pos, loc, playerid));
+        let hinted = view.hint.as_ref().and_then(|hint| hint.card);
+        self.hint = hinted.and_then(|id| self.deck.position(id));
+        for (i, card) in self.deck.iter().enumerate() {
+            if view.selection.contains(&card.id) {
+                self.selection.push(i)
+            }
+        }
+        self
+    }
+}
+
+impl DeckWidget<'_, '_> {
+    /// Renders a Deck
+    ///
+    /// If this Deck necessitate a popup it will return [true]
+    /// else it returns [fals€]
+    pub fn render(mut self, mut area: Rect, buf: &mut Buffer) -> bool
+    where
+        Self: Sized,
+    {
+        let mut need_popup = false;
+        if self.hidden && !self.selection.is_empty() {
+            area = self.popup_area.unwrap_or(buf.area);
+            self.hidden = false;
+            Clear.render(area, buf);
+            need_popup = true;
+        }
+        Block::bordered()
+            .title_top(Line::from(self.name).left_aligned())
+            .border_type(if !self.selection.is_empty() {
+                BorderType::Plain
+            } else {
+                BorderType::LightDoubleDashed
+            })
+            .border_style(if !self.selection.is_empty() {
+                Style::new().blue()
+            } else {
+                Style::new()
+            })
+            .render(area, buf);
+        let area = area.inner(Margin::new(1, 1));
+        if self.face_down || self.deck.iter().any(|card| card.is_hidden()) {
+            Paragraph::new(format!("{} cards", self.deck.len()))
+                .centered()
+                .render(area, buf);
+            return false;
+        }
+        if self.hidden {
+            Paragraph::new(format!("{}", self.deck.get_stats()))
+                .centered()
+                .render(area, buf);
+            return false;
+        }
+
+        let scroll = if need_popup { self.scroll } else { 0 };
+        let areas = if self.list {
+            list_areas(self.deck.len(), area, scroll)
+        } else {
+            card_areas(self.deck.len(), area, self.max_cols, scroll)
+        };
+        for (i, card_area) in areas {
+            self.deck[i]
+                .widget()
+                .set_selection(self.selection.contains(&i))
+                .set_hover(self.hover == Some(i))
+                .set_hint(self.hint == Some(i))
+                .set_dimmed(self.budget.is_some_and(|gold| self.deck[i].gold > gold))
+                .set_played(self.played)
+                .set_full_text(self.full_text)
+                .render(card_area, buf);
+        }
+        need_popup
+    }
+}
+
+/// Minimal height of a card displayed on a grid
+pub const CARD_MIN_HEIGHT: u16 = 5;
+
+/// Areas of the cards of a [Deck] of `len` cards displayed in `area`
+/// - with `max_cols` the cards are laid on a grid of rows of `max_cols` cards,
+///   `scroll` rows of the grid are hidden above the area when they do not fit
+/// - otherwise the cards are laid on a single row
+///
+/// Returns the index of each visible card with its area.
+pub fn card_areas(
+    len: usize,
+    area: Rect,
+    max_cols: Option<usize>,
+    scroll: usize,
+) -> Vec<(usize, Rect)> {
+    let Some(max_cols) = max_cols else {
+        return Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints(vec![Constraint::Fill(1); len])
+            .split(area)
+            .iter()
+            .copied()
+            .enumerate()
+            .collect();
+    };
+    let nb_rows = len / max_cols + 1;
+    let visible_rows = nb_rows.min(((area.height + 1) / (CARD_MIN_HEIGHT + 1)).max(1) as usize);
+    grid(
+        len,
+        area,
+        max_cols,
+        visible_rows,
+        Constraint::Fill(1),
+        1,
+        scroll,
+    )
+}
+
+/// Minimal width of a card displayed on a line of a list
+pub const LINE_MIN_WIDTH: u16 = 20;
+
+/// Areas of the lines of a [Deck] of `len` cards displayed as a list in `area`
+///
+/// The lines are split in columns when they do not fit in the height of the area,
+/// `scroll` rows are hidden above the area when the columns are not enough.
+pub fn list_areas(len: usize, area: Rect, scroll: usize) -> Vec<(usize, Rect)> {
+    let height = area.height.max(1) as usize;
+    let max_cols = (area.width / LINE_MIN_WIDTH).max(1) as usize;
+    let cols = len.div_ceil(height).clamp(1, max_cols);
+    let visible_rows = len.div_ceil(cols).clamp(1, height);
+    grid(
+        len,
+        area,
+        cols,
+        visible_rows,
+        Constraint::Length(1),
+        0,
+        scroll,
+    )
+}
+
+/// Areas of a grid of `cols` columns showing `visible_rows` rows of cards,
+/// `scroll` rows are hidden above the grid
+fn grid(
+    len: usize,
+    area: Rect,
+    cols: usize,
+    visible_rows: usize,
+    row: Constraint,
+    row_spacing: u16,
+    scroll: usize,
+) -> Vec<(usize, Rect)> {
+    let nb_rows = len / cols + 1;
+    let visible_rows = visible_rows.min(nb_rows);
+    let first_row = scroll.min(nb_rows - visible_rows);
+
+    let col_constraints = (0..cols).map(|_| Constraint::Fill(1));
+    let row_constraints = (0..visible_rows).map(|_| row);
+    let horizontal = Layout::horizontal(col_constraints).spacing(1);
+    let vertical = Layout::vertical(row_constraints).spacing(row_spacing);
+
+    vertical
+        .split(area)
+        .iter()
+        .flat_map(|&row| horizontal.split(row).to_vec())
+        .enumerate()
+        .map(|(i, card_area)| (first_row * cols + i, card_area))
+        .filter(|(i, _)| *i < len)
+        .collect()
+}
@@ -0,0 +1,420 @@
+use crate::{
+    game::{Game, POPUP_COLS},
+    player_id::PlayerId,
+    selection::{GamePosition, Location},
+    tui::deck::{card_areas, list_areas},
+    tui::view::View,
+};
+use ratatui::{
+    layout::{Constraint, Layout, Margin, Position},
+    prelude::{Buffer, Rect},
+    style::Style,
+    widgets::{Block, Paragraph, Widget, Wrap},
+};
+
+impl View {
+    /// Finds the card of the [Game] displayed at a position of the screen
+    ///
+    /// Remark: a hidden deck is found at the index 0,
+    /// its cards are found in its popup once it is selected.
+    pub fn position_at(&self, game: &Game, column: u16, row: u16) -> Option<GamePosition> {
+        use Location::*;
+        let point = Position::new(column, row);
+        let layout = GameLayout::new(self.area.get());
+        if layout.mode == LayoutMode::TooSmall {
+            return None;
+        }
+        let GamePosition { player, loc, .. } = self.position;
+
+        // The popup of a hidden deck covers the board
+        if let Discard | DrawPile = loc
+            && layout.popup.contains(point)
+        {
+            let len = game.zone(player, loc).len();
+            return layout
+                .popup_card_areas(len, self.scroll)
+                .into_iter()
+                .find(|(_, card)| card.contains(point))
+                .map(|(index, _)| GamePosition { player, loc, index });
+        }
+
+        let players = [game.current_player, game.current_player.other()];
+        let zones = [(PlayerId::First, Explorer), (PlayerId::First, Shop)]
+            .into_iter()
+            .chain(
+                players
+                    .into_iter()
+                    .flat_map(|player| [Hand, Played, Discard, DrawPile].map(|loc| (player, loc))),
+            );
+        for (player, loc) in zones {
+            let zone = layout.zone(game, player, loc);
+            if !zone.contains(point) {
+                continue;
+            }
+            let index = match loc {
+                Discard | DrawPile => 0,
+                _ => {
+                    let len = game.zone(player, loc).len();
+                    layout
+                        .card_areas(len, zone)
+                        .into_iter()
+                        .find(|(_, card)| card.contains(point))
+                        .map_or(0, |(index, _)| index)
+                }
+            };
+            return Some(GamePosition { player, loc, index });
+        }
+        None
+    }
+}
+
+impl Game {
+    /// Creates a [GameWidget] from a [Game] seen from a [View]
+    /// Remark: [GameWidget] implements the [Widget] trait and can be displayed
+    pub const fn widget<'a>(&'a self, view: &'a View) -> GameWidget<'a> {
+        GameWidget {
+            game: self,
+            view,
+            hidden_hands: [false, false],
+        }
+    }
+}
+
+/* Global Layout of the Game
+
+----------------------------------------|-----------|
+| Played hand opponent Player | discard | draw_pile |
+|----------|------------------|---------|-----------|
+| Explorer |       Shop       |    info players     |
+|----------|------------------|---------------------|
+|  Played Hand current player |     discard         |
+|-----------------------------|---------------------|
+|    Hand current Player      |      draw pile      |
+|-----------------------------|---------------------|
+
+Rmk: the left colum is about 5 times bigger than the right column
+*/
+
+/// Widget displaying a [Game]
+///
+/// Remark: the hand of a player can be hidden, for example
+/// when the [Game] is watched by a spectator.
+pub struct GameWidget<'a> {
+    game: &'a Game,
+    view: &'a View,
+    hidden_hands: [bool; 2],
+}
+
+impl GameWidget<'_> {
+    pub fn hide_hand(mut self, player: PlayerId) -> Self {
+        self.hidden_hands[player] = true;
+        self
+    }
+    pub fn hide_hands(self) -> Self {
+        Self {
+            hidden_hands: [true, true],
+            ..self
+        }
+    }
+}
+
+/// Renders a [Game] from a new [View], the cursor on the explorer
+impl Widget for &Game {
+    fn render(self, area: Rect, buf: &mut Buffer)
+    where
+        Self: Sized,
+    {
+        self.widget(&View::default()).render(area, buf)
+    }
+}
+
+/// Minimal size of the area of a [Game], a warning is shown instead in a smaller area
+pub const MIN_WIDTH: u16 = 60;
+pub const MIN_HEIGHT: u16 = 18;
+/// Width of the panel of the log, only shown when the board still fits next to it
+pub const LOG_WIDTH: u16 = 44;
+
+/// How the cards of a [Game] are displayed depending on the size of the terminal
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LayoutMode {
+    /// Smaller than [MIN_WIDTH] x [MIN_HEIGHT], nothing fits
+    TooSmall,
+    /// Each card is a line of a list
+    Compact,
+    /// Each card is a box with its actions as symbols
+    Normal,
+    /// Each card is a box with its actions as sentences
+    Wide,
+}
+
+impl LayoutMode {
+    /// The mode fitting in an area
+    pub const fn of(area: Rect) -> Self {
+        if area.width < MIN_WIDTH || area.height < MIN_HEIGHT {
+            LayoutMode::TooSmall
+        } else if area.width < 120 || area.height < 36 {
+            LayoutMode::Compact
+        } else if area.width >= 200 && area.height >= 50 {
+            LayoutMode::Wide
+        } else {
+            LayoutMode::Normal
+        }
+    }
+}
+
+/// Areas of the elements of a [Game] rendered in an area
+///
+/// The arrays are indexed by 0 for the current player at the bottom
+/// and 1 for its opponent at the top.
+#[derive(Debug, Clone, Copy)]
+pub struct GameLayout {
+    pub mode: LayoutMode,
+    /// Covered by the popup of a hidden deck, the side column stays visible
+    pub popup: Rect,
+    pub explorer: Rect,
+    pub shop: Rect,
+    pub played: [Rect; 2],
+    pub hand: [Rect; 2],
+    pub discard: [Rect; 2],
+    pub draw_pile: [Rect; 2],
+    pub info: [Rect; 2],
+    pub status_line: Rect,
+}
+
+impl GameLayout {
+    pub fn new(area: Rect) -> Self {
+        use Constraint::*;
+        let mode = LayoutMode::of(area);
+        let area = area.inner(Margin::new(1, 1));
+
+        // Layout the Grid, the lists of the compact mode need a wider side column
+        let horizontal = if mode == LayoutMode::Compact {
+            Layout::horizontal([Fill(3), Fill(1)])
+        } else {
+            Layout::horizontal([Fill(5), Fill(1)])
+        };
+        let vertical = Layout::vertical([Fill(1), Fill(1), Fill(1), Fill(1)]);
+        let status_line_layout = Layout::vertical([Fill(1), Length(1)]);
+        let shop_layout = Layout::horizontal([Fill(1), Fill(5)]);
+        let discard_draw_pile_layout = Layout::vertical([Fill(1), Fill(1)]);
+        let info_players_layout = Layout::vertical([Fill(1), Fill(1)]);
+
+        let layout = status_line_layout.split(area);
+        let (all_elements, status_line) = (layout[0], layout[1]);
+
+        let layout = horizontal.split(all_elements);
+        let (left, right) = (layout[0], layout[1]);
+
+        let layout = vertical.split(left);
+        let (played_op, explorer_shop, played_cur, hand_cur) =
+            (layout[0], layout[1], layout[2], layout[3]);
+
+        let layout = vertical.split(right);
+        let (discard_draw_pile_op, info_players, discard_cur, draw_pile_cur) =
+            (layout[0], layout[1], layout[2], layout[3]);
+
+        let layout = shop_layout.split(explorer_shop);
+        let (explorer, shop) = (layout[0], layout[1]);
+
+        let layout = discard_draw_pile_layout.split(discard_draw_pile_op);
+        let (draw_pile_op, discard_op) = (layout[0], layout[1]);
+
+        let layout = info_players_layout.split(info_players);
+
+        Self {
+            mode,
+            popup: left,
+            explorer,
+            shop,
+            played: [played_cur, played_op],
+            hand: [hand_cur, Rect::new(0, 0, 0, 0)],
+            discard: [discard_cur, discard_op],
+            draw_pile: [draw_pile_cur, draw_pile_op],
+            info: [layout[1], layout[0]],
+            status_line,
+        }
+    }
+    /// Area of the [Deck](crate::deck::Deck) at a [Location] of a player
+    pub fn zone(&self, game: &Game, player: PlayerId, loc: Location) -> Rect {
+        use Location::*;
+        let side = if player == game.current_player { 0 } else { 1 };
+        match loc {
+            Explorer => self.explorer,
+            Shop => self.shop,
+            Hand => self.hand[side],
+            Played => self.played[side],
+            Discard => self.discard[side],
+            DrawPile => self.draw_pile[side],
+        }
+    }
+    /// Areas of the cards of a [Deck](crate::deck::Deck) of `len` cards shown in a `zone`
+    pub fn card_areas(&self, len: usize, zone: Rect) -> Vec<(usize, Rect)> {
+        let cards = zone.inner(Margin::new(1, 1));
+        if self.mode == LayoutMode::Compact {
+            list_areas(len, cards, 0)
+        } else {
+            card_areas(len, cards, None, 0)
+        }
+    }
+    /// Areas of the cards of a hidden [Deck](crate::deck::Deck) of `len` cards shown in the popup
+    pub fn popup_card_areas(&self, len: usize, scroll: usize) -> Vec<(usize, Rect)> {
+        let cards = self.popup.inner(Margin::new(1, 1));
+        if self.mode == LayoutMode::Compact {
+            list_areas(len, cards, scroll)
+        } else {
+            card_areas(len, cards, Some(POPUP_COLS), scroll)
+        }
+    }
+}
+
+impl Widget for GameWidget<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer)
+    where
+        Self: Sized,
+    {
+        let (game, view) = (self.game, self.view);
+        let area = if view.show_log && area.width >= MIN_WIDTH + LOG_WIDTH {
+            let [board, log] =
+                Layout::horizontal([Constraint::Fill(1), Constraint::Length(LOG_WIDTH)])
+                    .areas(area);
+            game.log_widget(view.log_scroll).render(log, buf);
+            board
+        } else {
+            area
+        };
+        view.area.set(area);
+        if LayoutMode::of(area) == LayoutMode::TooSmall {
+            let warning = format!(
+                "The terminal is too small: {}x{}, the game needs {MIN_WIDTH}x{MIN_HEIGHT}",
+                area.width, area.height
+            );
+            Paragraph::new(warning)
+                .centered()
+                .wrap(Wrap { trim: true })
+                .style(Style::new().yellow())
+                .render(area, buf);
+            return;
+        }
+        self.render_board(area, buf);
+
+        // Zoom on the card under the cursor
+        let position = &view.position;
+        let hidden = position.loc == Location::Hand && self.hidden_hands[position.player];
+        if view.zoom
+            && !hidden
+            && let Some(card) = game.card_at(position)
+            && !card.is_hidden()
+        {
+            card.zoom_widget().render(area, buf);
+        }
+    }
+}
+
+impl GameWidget<'_> {
+    /// Renders the decks of the [Game] and its status line
+    fn render_board(&self, area: Rect, buf: &mut Buffer) {
+        let game = self.game;
+        Block::bordered().render(area, buf);
+        let GameLayout {
+            mode,
+            popup: popup_area,
+            explorer,
+            shop,
+            played: played_players,
+            hand: hand_players,
+            discard: discard_players,
+            draw_pile: draw_pile_players,
+            info: info_players,
+            status_line,
+        } = GameLayout::new(area);
+
+        // Shop, the cards the current player cannot afford are dimmed
+        let gold = game.players[game.current_player].gold;
+        game.explorer
+            .widget()
+            .set_name("Explorer")
+            .set_layout(mode)
+            .set_budget(gold)
+            .set_selection(self.view, Location::Explorer, PlayerId::First)
+            .render(explorer, buf);
+        game.shop
+            .widget()
+            .set_name("Shop")
+            .set_layout(mode)
+            .set_budget(gold)
+            .set_selection(self.view, Location::Shop, PlayerId::First)
+            .render(shop, buf);
+
+        // The two players,
+        // 0 is at the bottom is the current player
+        // 1 is at the top
+        let ids = [game.current_player, game.current_player.other()];
+        for (side, id) in ids.into_iter().enumerate() {
+            let player = &game.players[id];
+
+            // Played Hand of the player
+            player
+                .played
+                .widget()
+                .set_name(&format!("Played {} player", id))
+                .set_layout(mode)
+                .set_played(&player.played)
+                .set_selection(self.view, Location::Played, id)
+                .render(played_players[side], buf);
+            // Hand of the player
+            let hand_name = format!("Hand {} player", id);
+            let hand_widget = player
+                .hand
+                .widget()
+                .set_name(&hand_name)
+                .set_layout(mode)
+                .set_played(&player.played)
+                .set_selection(self.view, Location::Hand, id);
+            if self.hidden_hands[id] {
+                hand_widget.face_down().render(hand_players[side], buf);
+            } else {
+                hand_widget.render(hand_players[side], buf);
+            }
+
+            // Draw info about current player, on a single line in the compact mode
+            let info_text = if mode == LayoutMode::Compact {
+                format!("{}", player).replace('\n', " ")
+            } else {
+                format!("{}", player)
+            };
+            Paragraph::new(info_text)
+                .centered()
+                .render(info_players[side], buf);
+        }
+
+        // Status Line
+        Paragraph::new(format!("{}", self.view.status_line(game))).render(status_line, buf);
+
+        // The hidden decks are rendered last, the popup of a selected one covers the board
+        for (side, id) in ids.into_iter().enumerate() {
+            let player = &game.players[id];
+            // Draw pile of the player
+            player
+                .draw_pile
+                .widget()
+                .set_name(&format!("Draw Pile {} player", id))
+                .set_max_cols(POPUP_COLS)
+                .set_layout(mode)
+                .set_popup_area(popup_area)
+                .set_selection(self.view, Location::DrawPile, id)
+                .hidden()
+                .render(draw_pile_players[side], buf);
+            // Discard of the player
+            player
+                .discard
+                .widget()
+                .set_name(&format!("Discard {} player", id))
+                .set_max_cols(POPUP_COLS)
+                .set_layout(mode)
+                .set_popup_area(popup_area)
+                .set_selection(self.view, Location::Discard, id)
+                .hidden()
+                .render(discard_players[side], buf);
+        }
+    }
+}
@@ -0,0 +1,34 @@
+use crate::hot_seat::HotSeat;
+use ratatui::{
+    layout::Constraint,
+    prelude::{Buffer, Rect},
+    style::Style,
+    widgets::{Block, BorderType, Clear, Paragraph, Widget},
+};
+
+impl Widget for &HotSeat {
+    fn render(self, area: Rect, buf: &mut Buffer)
+    where
+        Self: Sized,
+    {
+        let Some(player) = self.waiting else {
+            self.game.widget(&self.view).render(area, buf);
+            return;
+        };
+        self.game.widget(&self.view).hide_hands().render(area, buf);
+
+        let popup = area.centered(Constraint::Length(44), Constraint::Length(5));
+        Clear.render(popup, buf);
+        Paragraph::new(format!(
+            "Pass the keyboard to the {} player\n\n[enter] show my hand",
+            player
+        ))
+        .centered()
+        .block(
+            Block::bordered()
+                .border_type(BorderType::Double)
+                .border_style(Style::new().blue()),
+        )
+        .render(popup, buf);
+    }
+}
@@ -0,0 +1,48 @@
+use crate::{game::Game, log::LogEntry};
+use ratatui::{
+    layout::Margin,
+    prelude::{Buffer, Rect},
+    style::Style,
+    text::Line,
+    widgets::{Block, Paragraph, Widget},
+};
+
+impl Game {
+    /// Creates a [LogWidget] from the [log](Game::log) of a [Game],
+    /// `scroll` entries hidden below the panel
+    pub fn log_widget(&self, scroll: usize) -> LogWidget<'_> {
+        LogWidget {
+            log: &self.log,
+            scroll,
+        }
+    }
+}
+
+/// Panel with the last entries of a log, the newest at the bottom
+pub struct LogWidget<'a> {
+    log: &'a [LogEntry],
+    /// Entries hidden below the panel
+    scroll: usize,
+}
+
+impl Widget for LogWidget<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer)
+    where
+        Self: Sized,
+    {
+        let height = area.inner(Margin::new(1, 1)).height as usize;
+        let end = self.log.len() - self.scroll.min(self.log.len());
+        let start = end.saturating_sub(height);
+        let lines: Vec<Line> = self.log[start..end]
+            .iter()
+            .map(|entry| Line::from(entry.to_string()))
+            .collect();
+        let mut block = Block::bordered().title_top(Line::from("Log").left_aligned());
+        if self.scroll != 0 {
+            block = block.title_bottom(Line::from(format!("{} more", self.scroll)).right_aligned());
+        }
+        Paragraph::new(lines)
+            .block(block.border_style(Style::new().blue()))
+            .render(area, buf);
+    }
+}
@@ -0,0 +1,475 @@
+use crate::{
+    ai::{self, Hint},
+    card::CardId,
+    event::{Event, Rejected},
+    game::{Game, POPUP_COLS, Rules},
+    input::Input,
+    player_id::PlayerId,
+    selection::{GamePosition, Location},
+    state::State,
+};
+use rand::{Rng, RngExt};
+use ratatui::{
+    crossterm::event::{MouseButton, MouseEvent, MouseEventKind},
+    prelude::Rect,
+};
+use std::{cell::Cell, fmt::Display};
+
+/// What a player sees of a [Game] in the terminal
+///
+/// The rules do not depend on it: the cursor, the chosen cards and
+/// the panels shown are only used to build the [Event]s from the [Input]s.
+#[derive(Debug, Clone, Default)]
+pub struct View {
+    /// Cursor of the player, moved by the [Input]s
+    pub position: GamePosition,
+    /// Cards chosen for the action asking for a choice
+    pub selection: Vec<CardId>,
+    /// Card under the mouse
+    pub hover: Option<GamePosition>,
+    /// Show the details of the card under the cursor
+    pub zoom: bool,
+    /// Suggested next move, until an event is played
+    pub hint: Option<Hint>,
+    /// Show the [log](Game::log) next to the board
+    pub show_log: bool,
+    /// Entries of the log scrolled out at the bottom of its panel
+    pub log_scroll: usize,
+    /// Rows of cards scrolled in the popup of a hidden deck
+    pub scroll: usize,
+    /// Area where the [Game] was last rendered, to find the card under the mouse
+    pub area: Cell<Rect>,
+}
+
+/// What the mouse does at a position of the terminal
+///
+/// Remark: only the [View] knows where the cards are displayed,
+/// the scrolling of the wheel is an [Input].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mouse {
+    /// Left click
+    Click { column: u16, row: u16 },
+    /// The mouse moved
+    Hover { column: u16, row: u16 },
+}
+
+impl Mouse {
+    /// The click or the move of a terminal [MouseEvent]
+    pub fn from(mouse_event: MouseEvent) -> Option<Self> {
+        let (column, row) = (mouse_event.column, mouse_event.row);
+        match mouse_event.kind {
+            MouseEventKind::Down(MouseButton::Left) => Some(Mouse::Click { column, row }),
+            MouseEventKind::Moved => Some(Mouse::Hover { column, row }),
+            _ => None,
+        }
+    }
+    pub fn random<R: Rng>(rng: &mut R) -> Self {
+        let (column, row) = (rng.random_range(0..200), rng.random_range(0..60));
+        if rng.random() {
+            Mouse::Click { column, row }
+        } else {
+            Mouse::Hover { column, row }
+        }
+    }
+}
+
+/// Writes a [Mouse] move with its position, for example `click 12 4`
+impl Display for Mouse {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Mouse::Click { column, row } => write!(f, "click {column} {row}"),
+            Mouse::Hover { column, row } => write!(f, "hover {column} {row}"),
+        }
+    }
+}
+
+impl std::str::FromStr for Mouse {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut words = s.split_whitespace();
+        let name = words.next().unwrap_or("");
+        let mut coordinate = || {
+            words
+                .next()
+                .and_then(|word| word.parse().ok())
+                .ok_or_else(|| format!("expected '{name} column row'"))
+        };
+        match name {
+            "click" => Ok(Mouse::Click {
+                column: coordinate()?,
+                row: coordinate()?,
+            }),
+            "hover" => Ok(Mouse::Hover {
+                column: coordinate()?,
+                row: coordinate()?,
+            }),
+            _ => Err(format!("unknown mouse move '{s}'")),
+        }
+    }
+}
+
+/// Something a player does in the terminal: an [Input] or a [Mouse] move
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Gesture {
+    Input(Input),
+    Mouse(Mouse),
+}
+
+impl Gesture {
+    pub fn random<R: Rng>(rng: &mut R) -> Self {
+        // One gesture out of nine is a mouse move
+        if rng.random_range(0..9) == 0 {
+            Gesture::Mouse(Mouse::random(rng))
+        } else {
+            Gesture::Input(Input::random(rng))
+        }
+    }
+}
+
+impl Display for Gesture {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Gesture::Input(input) => write!(f, "{input}"),
+            Gesture::Mouse(mouse) => write!(f, "{mouse}"),
+        }
+    }
+}
+
+impl std::str::FromStr for Gesture {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.split_whitespace().next() {
+            Some("click" | "hover") => s.parse().map(Gesture::Mouse),
+            _ => s.parse().map(Gesture::Input),
+        }
+    }
+}
+
+impl View {
+    /// Get the event from doing an action
+    /// thas usually comes from pressing the enter button.
+    pub fn do_action(&self, game: &Game) -> Option<Event> {
+        use Event::*;
+        use State::*;
+        let attack = game.players[game.current_player].attack;
+        match game.state {
+            Playing => {
+                if attack == 0 {
+                    Some(NextTurn)
+                } else {
+                    Some(Attack)
+                }
+            }
+            Scraping { .. } | Discarding { .. } | Copy => Some(Choose(self.selection.clone())),
+            WonBy(_) => None,
+        }
+    }
+
+    /// Get the event from doing a selection
+    /// that usually comes from pressing the space bar.
+    pub fn do_selection(&mut self, game: &Game) -> Option<Event> {
+        use Location::*;
+        use State::*;
+        let id = game.card_at(&self.position).map(|card| card.id);
+        match game.state {
+            Copy | Discarding { .. } | Scraping { .. } => {
+                if let Some(id) = id {
+                    if let Some(index) = self.selection.iter().position(|chosen| *chosen == id) {
+                        self.selection.remove(index);
+                    } else {
+                        self.selection.push(id);
+                    }
+                }
+                return None;
+            }
+            _ => (),
+        }
+        match self.position.loc {
+            Explorer => Some(Event::BuyExplorer),
+            Shop => id.map(Event::Buy),
+            Hand => id.map(Event::Play),
+            Played => id.map(Event::Activate),
+            Discard => None,
+            DrawPile => None,
+        }
+    }
+    /// Given the [Game] state
+    /// returns an [Event] from an [Input]
+    pub fn get_event(&mut self, game: &Game, input: Input) -> Option<Event> {
+        use Input::*;
+        let shown = (self.position.player, self.position.loc);
+        let event = match input {
+            Enter => self.do_action(game),
+            Space => self.do_selection(game),
+            Left => {
+                if self.position.index == 0 {
+                    self.position.next_left();
+                } else {
+                    self.position.index -= 1;
+                }
+                None
+            }
+            Right => {
+                if self.position.index + 1
+                    >= game.zone(self.position.player, self.position.loc).len()
+                {
+                    self.position.next_right();
+                } else {
+                    self.position.index += 1;
+                }
+                None
+            }
+            Up => {
+                self.position.next_up(&game.current_player);
+                None
+            }
+            Down => {
+                self.position.next_down(&game.current_player);
+                None
+            }
+            EndTurn => match game.state {
+                State::Playing => Some(Event::NextTurn),
+                _ => None,
+            },
+            PlayAll => match game.state {
+                State::Playing => Some(Event::PlayAll),
+                _ => None,
+            },
+            Cancel => {
+                self.selection.clear();
+                None
+            }
+            Zoom => {
+                self.zoom = !self.zoom;
+                None
+            }
+            Hint => {
+                self.hint = ai::hint(game);
+                None
+            }
+            Undo | Help | Quit => None,
+            // The popup of a hidden deck is scrolled before the log
+            ScrollUp if self.show_log && !self.position.loc.is_hidden() => {
+                self.log_scroll = (self.log_scroll + 1).min(game.log.len().saturating_sub(1));
+                None
+            }
+            ScrollDown if self.show_log && !self.position.loc.is_hidden() => {
+                self.log_scroll = self.log_scroll.saturating_sub(1);
+                None
+            }
+            ScrollUp => {
+                self.scroll = self.scroll.saturating_sub(1);
+                None
+            }
+            ScrollDown => {
+                let len = game.zone(self.position.player, self.position.loc).len();
+                self.scroll = (self.scroll + 1).min(len / POPUP_COLS);
+                None
+            }
+            Log => {
+                self.show_log = !self.show_log;
+                self.log_scroll = 0;
+                None
+            }
+            Other => None,
+        };
+        self.moved(game, shown);
+        event
+    }
+    /// Given the [Game] state
+    /// returns an [Event] from a [Mouse] move
+    pub fn get_mouse_event(&mut self, game: &Game, mouse: Mouse) -> Option<Event> {
+        let shown = (self.position.player, self.position.loc);
+        let event = match mouse {
+            Mouse::Click { column, row } => match self.position_at(game, column, row) {
+                Some(position) => {
+                    self.position = position;
+                    self.do_selection(game)
+                }
+                None => None,
+            },
+            Mouse::Hover { column, row } => {
+                self.hover = self.position_at(game, column, row);
+                None
+            }
+        };
+        self.moved(game, shown);
+        event
+    }
+    /// Keeps the cursor off the hand of the opponent,
+    /// the popup is scrolled back to its top when another deck is `shown`
+    fn moved(&mut self, game: &Game, shown: (PlayerId, Location)) {
+        if self.position.player == game.current_player.other()
+            && self.position.loc == Location::Hand
+        {
+            self.position.loc = Location::Played;
+            self.position.index = 0;
+        }
+        if shown != (self.position.player, self.position.loc) {
+            self.scroll = 0;
+        }
+    }
+    /// Plays an [Input] on a [Game], the last event of the turn is undone by [Input::Undo]
+    pub fn interact(&mut self, game: &mut Game, input: Input) {
+        if let Input::Undo = input {
+            let _ = self.undo(game);
+            return;
+        }
+        if let Some(event) = self.get_event(game, input) {
+            let _ = self.apply(game, event);
+        }
+    }
+    /// Plays a [Gesture] on a [Game]
+    pub fn gesture(&mut self, game: &mut Game, gesture: Gesture) {
+        match gesture {
+            Gesture::Input(input) => self.interact(game, input),
+            Gesture::Mouse(mouse) => {
+                if let Some(event) = self.get_mouse_event(game, mouse) {
+                    let _ = self.apply(game, event);
+                }
+            }
+        }
+    }
+    /// Applies an [Event] built by this [View] to the [Game]
+    ///
+    /// The chosen cards are forgotten once the choice is accepted.
+    pub fn apply(&mut self, game: &mut Game, event: Event) -> Result<(), Rejected> {
+        let player = game.current_player;
+        let result = game.apply_event(event.clone());
+        if result.is_ok() {
+            match event {
+                Event::Choose(_) => self.selection.clear(),
+                Event::PlayAll
+                    if self.position.player == player && self.position.loc == Location::Hand =>
+                {
+                    self.position.index = 0
+                }
+                _ => (),
+            }
+            self.hint = None;
+        }
+        self.clamp(game);
+        result
+    }
+    /// Undoes the last [Event] of the turn, see [Game::undo]
+    pub fn undo(&mut self, game: &mut Game) -> Result<(), Rejected> {
+        game.undo()?;
+        self.refresh(game);
+        Ok(())
+    }
+    /// Follows a [Game] changed by someone else: the hint is dropped
+    /// and the cursor is kept in its deck
+    pub fn refresh(&mut self, game: &Game) {
+        self.hint = None;
+        self.clamp(game);
+    }
+    /// The cards have moved, keeps the cursor in its deck
+    fn clamp(&mut self, game: &Game) {
+        let len = game.zone(self.position.player, self.position.loc).len();
+        if self.position.index >= len {
+            self.position.index = len.saturating_sub(1);
+        }
+        self.log_scroll = self.log_scroll.min(game.log.len());
+    }
+    /// The cursor is in its [Deck](crate::deck::Deck), at the index 0 of an empty one,
+    /// and the selected cards are in the game
+    pub fn check(&self, game: &Game) -> Result<(), String> {
+        let len = game.zone(self.position.player, self.position.loc).len();
+        if self.position.index >= len && self.position.index != 0 {
+            return Err(format!("the cursor is out of its deck: {}", self.position));
+        }
+        for id in &self.selection {
+            if game.position_of(*id).is_none() {
+                return Err(format!("the selected card {id} is not in the game"));
+            }
+        }
+        Ok(())
+    }
+    pub fn status_line<'a>(&'a self, game: &'a Game) -> impl Display + 'a {
+        StatusLine(game, self)
+    }
+}
+
+pub struct StatusLine<'a>(&'a Game, &'a View);
+impl Display for StatusLine<'_> {
+    // todo: better adapt on the state of the game and precise
+    // position of cursor
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        use Location::*;
+        use State::*;
+        let Rules {
+            players,
+            state,
+            current_player,
+            ..
+        } = &self.0.rules;
+        let View {
+            position,
+            selection,
+            hint,
+            ..
+        } = self.1;
+
+        if let State::WonBy(playerid) = state {
+            write!(f, "\t>>>>\t{} Player WON\t<<<<\t", playerid)?;
+            return Ok(());
+        }
+        if let Some(hint) = hint {
+            return write!(f, "[hint] {}", hint.reason);
+        }
+
+        write!(f, "[->][↑][<-][↓] move")?;
+        write!(f, " | ")?;
+        write!(f, "[enter] ")?;
+        let width_enter = 20;
+        match state {
+            Playing => {
+                if players[*current_player].attack == 0 {
+                    write!(f, "{:^width$}", "Next Turn", width = width_enter)?;
+                } else {
+                    write!(f, "{:^width$}", "Attack Opponent", width = width_enter)?;
+                }
+            }
+            Scraping { .. } => write!(
+                f,
+                "{:^width$}",
+                format!("Scrap {} cards selectioned", selection.len()),
+                width = width_enter,
+            )?,
+            Discarding { .. } => write!(
+                f,
+                "{:^width$}",
+                format!("Discard {} cards selectioned", selection.len()),
+                width = width_enter
+            )?,
+            Copy => write!(
+                f,
+                "{:^width$}",
+                "Copy Ship selectioned",
+                width = width_enter
+            )?,
+            _ => (),
+        }
+        write!(f, " | ")?;
+        write!(f, "[space] ")?;
+
+        match state {
+            Playing => match position.loc {
+                Explorer => write!(f, "Buy an Explorer")?,
+                Shop => write!(f, "Buy a card from the shop")?,
+                Hand => write!(f, "Play a card from your hand")?,
+                Played => write!(f, "Activate this card")?,
+                Discard => write!(f, "")?,
+                DrawPile => write!(f, "")?,
+            },
+            Scraping { nb, loc } => write!(f, "Scrapping {} cards at {}", nb, loc)?,
+            Discarding { nb, loc } => write!(f, "Discarding {} cards at {}", nb, loc)?,
+            Copy => write!(f, "")?,
+            _ => (),
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,113 @@
+#![cfg(feature = "tui")]
+
+use stars_gapa::{
+    app::{App, Exit, Screen},
+    bindings::Bindings,
+    input::Input,
+    state::State,
+};
+
+fn choose(app: &mut App, item: usize) {
+    for _ in 0..10 {
+        app.interact(Input::Up);
+    }
+    for _ in 0..item {
+        app.interact(Input::Down);
+    }
+    app.interact(Input::Enter);
+}
+
+#[test]
+pub fn menus_and_game_against_the_computer() {
+    let data_dir = std::env::temp_dir().join(format!("stars-gapa-app-{}", std::process::id()));
+    let mut app = App::new(Bindings::default(), &data_dir);
+
+    // The card catalog
+    choose(&mut app, 6);
+    let Screen::Catalog(menu, cards) = app.screen() else {
+        panic!("Expected the card catalog");
+    };
+    assert_eq!(menu.items.len(), cards.len());
+    assert!(menu.items.iter().any(|name| name == "Explorer"));
+    app.interact(Input::Quit);
+    assert!(matches!(app.screen(), Screen::MainMenu(_)));
+
+    // A game against the computer is paused and saved
+    choose(&mut app, 0);
+    assert!(matches!(app.screen(), Screen::Match(_)));
+    app.interact(Input::Quit);
+    choose(&mut app, 1);
+    let Screen::Pause(menu) = app.screen() else {
+        panic!("Expected the pause menu");
+    };
+    assert!(menu.message.as_ref().unwrap().starts_with("Saved"));
+    choose(&mut app, 0);
+
+    // The computer wins against a player that never attacks
+    for _ in 0..100_000 {
+        match app.screen() {
+            Screen::Match(game) if game.game().current_player == computer(game) => app.tick(),
+            // The discards asked by the computer are confirmed
+            Screen::Match(game) if !matches!(game.game().state, State::Playing) => {
+                app.interact(Input::Enter)
+            }
+            Screen::Match(_) => app.interact(Input::EndTurn),
+            _ => break,
+        }
+    }
+    let Screen::GameOver(menu) = app.screen() else {
+        panic!("Expected the game over screen, got {:?}", app.screen());
+    };
+    assert_eq!(menu.title, "The computer won");
+
+    // Rematch then go back to the main menu
+    choose(&mut app, 0);
+    let Screen::Match(game) = app.screen() else {
+        panic!("Expected a rematch");
+    };
+    assert!(matches!(game.game().state, State::Playing));
+    app.interact(Input::Quit);
+    choose(&mut app, 3);
+    assert!(matches!(app.screen(), Screen::MainMenu(_)));
+
+    // The saved game is loaded
+    choose(&mut app, 4);
+    let Screen::Load(menu, _) = app.screen() else {
+        panic!("Expected the saved games");
+    };
+    assert_eq!(menu.items.len(), 1);
+    app.interact(Input::Enter);
+    assert!(matches!(app.screen(), Screen::Match(_)));
+    app.interact(Input::Quit);
+    choose(&mut app, 3);
+
+    // The finished game is replayed
+    choose(&mut app, 5);
+    app.interact(Input::Enter);
+    let Screen::Replay(replay) = app.screen() else {
+        panic!("Expected a replay");
+    };
+    assert_eq!(replay.step, 0);
+    let events = replay.record.events.len();
+    for _ in 0..events + 3 {
+        app.interact(Input::Right);
+    }
+    let Screen::Replay(replay) = app.screen() else {
+        panic!("Expected a replay");
+    };
+    assert_eq!(replay.step, events);
+    assert!(matches!(replay.game.state, State::WonBy(_)));
+    app.interact(Input::Quit);
+    app.interact(Input::Quit);
+
+    app.interact(Input::Quit);
+    assert_eq!(app.take_exit(), Some(Exit::Quit));
+    std::fs::remove_dir_all(data_dir).unwrap();
+}
+
+fn computer(game: &stars_gapa::app::Match) -> stars_gapa::player_id::PlayerId {
+    match game.mode {
+        stars_gapa::app::Mode::Computer(computer) => computer,
+        stars_gapa::app::Mode::HotSeat => panic!("Expected a game against the computer"),
+    }
+}
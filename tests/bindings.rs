@@ -0,0 +1,63 @@
+#![cfg(feature = "tui")]
+
+use ratatui::crossterm::event::{Event, KeyCode, KeyEvent};
+use stars_gapa::{
+    bindings::Bindings,
+    event::{Event as GameEvent, Rejected},
+    game::Game,
+    input::Input,
+    tui::view::View,
+};
+
+fn key(code: KeyCode) -> Event {
+    Event::Key(KeyEvent::from(code))
+}
+
+#[test]
+pub fn bindings_file() {
+    let bindings: Bindings = "
+        # vim users
+        undo = u Backspace
+        end-turn = F5
+    "
+    .parse()
+    .unwrap();
+    assert_eq!(bindings.input(&key(KeyCode::Backspace)), Input::Undo);
+    assert_eq!(bindings.input(&key(KeyCode::F(5))), Input::EndTurn);
+    // Backspace is not bound to cancel anymore
+    assert_eq!(bindings.input(&key(KeyCode::Char('c'))), Input::Cancel);
+    assert_eq!(bindings.keys(Input::Cancel), &[KeyCode::Char('c')]);
+    assert_eq!(bindings.input(&key(KeyCode::Char('e'))), Input::Other);
+    // The other inputs keep their default keys
+    assert_eq!(bindings.input(&key(KeyCode::Char('h'))), Input::Left);
+
+    // The bindings are written in the format of the file
+    assert_eq!(format!("{bindings}").parse::<Bindings>(), Ok(bindings));
+
+    assert!("undo u".parse::<Bindings>().is_err());
+    assert!("redo = r".parse::<Bindings>().is_err());
+    assert!("undo = Ctrl".parse::<Bindings>().is_err());
+}
+
+#[test]
+pub fn undo_and_play_all() {
+    let mut game = Game::with_seed(3);
+    let mut view = View::default();
+    let hand = game.players[game.current_player].hand.len();
+
+    view.interact(&mut game, Input::PlayAll);
+    assert_eq!(game.historic, vec![GameEvent::PlayAll]);
+    assert!(game.players[game.current_player].hand.is_empty());
+
+    // The game is replayed without the last event
+    view.interact(&mut game, Input::Undo);
+    assert!(game.historic.is_empty());
+    assert_eq!(game.players[game.current_player].hand.len(), hand);
+    assert_eq!(game.state_hash(), Game::with_seed(3).state_hash());
+
+    // The previous turns cannot be undone
+    view.interact(&mut game, Input::EndTurn);
+    let hash = game.state_hash();
+    assert_eq!(game.undo(), Err(Rejected::NothingToUndo));
+    assert_eq!(game.state_hash(), hash);
+}
@@ -0,0 +1,131 @@
+use stars_gapa::{
+    bot::{self, Bot, BotError, Outcome, Seat},
+    event::Event,
+    game::Game,
+    player_id::PlayerId,
+};
+use std::time::Duration;
+
+/// A bot in shell playing the first legal move
+const FIRST_MOVE: &str = r#"
+while read -r line; do
+    case "$line" in
+        hello*) echo ready ;;
+        "move "*) [ -z "$move" ] && move="${line#move }" ;;
+        go) echo "$move"; move= ;;
+    esac
+done"#;
+
+fn spawn(command: &str, seat: PlayerId) -> Result<Bot, BotError> {
+    Bot::spawn(command, seat, Duration::from_millis(500))
+}
+
+#[test]
+pub fn bots_see_their_position() {
+    let game = Game::with_seed(3);
+    let legal = game.legal_events();
+    let player = &game.players[game.current_player];
+    assert!(legal.contains(&Event::BuyExplorer) == (player.gold >= 2));
+    assert!(legal.contains(&Event::Play(player.hand[0].id)));
+    assert!(legal.contains(&Event::NextTurn));
+    assert!(!legal.contains(&Event::Attack));
+
+    let text = bot::position(&game, game.current_player, &legal).to_string();
+    let lines: Vec<_> = text.lines().collect();
+    assert_eq!(lines[..3], ["position", "turn 1", "state playing"]);
+    assert_eq!(lines.last(), Some(&"go"));
+    let hand = format!("hand #{}", player.hand[0].id.0);
+    assert!(lines.iter().any(|line| line.starts_with(&hand)));
+    assert!(lines.contains(&"opponent-hand 0"));
+    assert!(lines.contains(&"move end"));
+}
+
+#[test]
+pub fn bots_play_a_match() {
+    let mut game = Game::with_seed(5);
+    let seats = [
+        Seat::Computer,
+        Seat::Bot(spawn(FIRST_MOVE, PlayerId::Second).unwrap()),
+    ];
+    let outcome = bot::play_match(&mut game, seats, 500);
+    assert!(matches!(outcome, Outcome::Won(_)), "{outcome}");
+}
+
+#[test]
+pub fn bots_read_the_winner() {
+    let path = std::env::temp_dir().join(format!("stars-gapa-gameover-{}", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+    // The first legal move, the winner is written a bit after the input is closed
+    let bot = format!(
+        r#"
+while read -r line; do
+    case "$line" in
+        hello*) echo ready ;;
+        "move "*) [ -z "$move" ] && move="${{line#move }}" ;;
+        go) echo "$move"; move= ;;
+        gameover*) winner="$line" ;;
+    esac
+done
+sleep 0.1
+echo "$winner" > '{}'"#,
+        path.display()
+    );
+    let mut game = Game::with_seed(5);
+    let seats = [
+        Seat::Computer,
+        Seat::Bot(spawn(&bot, PlayerId::Second).unwrap()),
+    ];
+    let Outcome::Won(winner) = bot::play_match(&mut game, seats, 500) else {
+        panic!("the game is not won");
+    };
+    let written = std::fs::read_to_string(&path).unwrap();
+    let _ = std::fs::remove_file(&path);
+    assert_eq!(written.trim(), format!("gameover {winner}"));
+}
+
+#[test]
+pub fn bad_bots_forfeit() {
+    // Illegal moves
+    let illegal = r#"
+echo ready
+while read -r line; do
+    case "$line" in
+        go | illegal*) echo 'play #999' ;;
+    esac
+done"#;
+    let mut game = Game::with_seed(5);
+    let seats = [
+        Seat::Bot(spawn(illegal, PlayerId::First).unwrap()),
+        Seat::Computer,
+    ];
+    let outcome = bot::play_match(&mut game, seats, 500);
+    let loser = game.current_player;
+    assert_eq!(
+        outcome,
+        Outcome::Forfeit {
+            loser,
+            error: BotError::Illegal(String::from("play #999"))
+        }
+    );
+
+    // Too slow
+    let mut game = Game::with_seed(5);
+    let seats = [
+        Seat::Bot(spawn("echo ready; sleep 5", PlayerId::First).unwrap()),
+        Seat::Bot(spawn("echo ready; sleep 5", PlayerId::Second).unwrap()),
+    ];
+    let outcome = bot::play_match(&mut game, seats, 500);
+    assert!(matches!(
+        outcome,
+        Outcome::Forfeit {
+            error: BotError::Timeout,
+            ..
+        }
+    ));
+
+    // Stopped before being ready
+    assert_eq!(
+        spawn("true", PlayerId::First).unwrap_err(),
+        BotError::Stopped
+    );
+}
@@ -0,0 +1,104 @@
+#![cfg(feature = "tui")]
+
+mod common;
+
+use ratatui::{
+    buffer::Buffer,
+    layout::{Margin, Rect},
+    style::{Color, Modifier},
+    widgets::Widget,
+};
+use stars_gapa::{
+    action::{Action, Condition},
+    app::catalog,
+    card::CardId,
+    deck::Deck,
+    faction::Faction,
+    game::Game,
+    player_id::PlayerId,
+    selection::Location,
+    theme::{Palette, Theme},
+    tui::{deck::card_areas, game::GameLayout},
+};
+
+/// Checks if a cell inside `area` has the `modifier`
+fn has_modifier(buf: &Buffer, area: Rect, modifier: Modifier) -> bool {
+    area.positions()
+        .any(|position| buf[position].modifier.contains(modifier))
+}
+
+#[test]
+pub fn shop_and_allies() {
+    let _theme = common::theme_lock(Theme::Emoji, Palette::Normal);
+    let area = Rect::new(0, 0, 200, 60);
+    let mut game = Game::with_seed(7);
+    let mut buf = Buffer::empty(area);
+    game.render(area, &mut buf);
+
+    // Nothing is affordable without gold
+    let layout = GameLayout::new(area);
+    let shop = layout.zone(&game, PlayerId::First, Location::Shop);
+    let (_, card) = card_areas(game.shop.len(), shop.inner(Margin::new(1, 1)), None, 0)[0];
+    assert!(has_modifier(
+        &buf,
+        card.inner(Margin::new(1, 1)),
+        Modifier::DIM
+    ));
+
+    let player = game.current_player;
+    game.players[player].gold = 100;
+    let mut buf = Buffer::empty(area);
+    game.render(area, &mut buf);
+    assert!(!has_modifier(
+        &buf,
+        card.inner(Margin::new(1, 1)),
+        Modifier::DIM
+    ));
+
+    // A card with an ally ability of its own faction
+    let card = catalog()
+        .into_iter()
+        .find(|card| {
+            card.iter().any(|action| {
+                matches!(
+                    action,
+                    Action::Complex {
+                        cond: Condition::FactionPlayed(factions),
+                        ..
+                    } if factions.first().map(Faction::to_usize)
+                        == card.faction.first().map(Faction::to_usize)
+                )
+            })
+        })
+        .unwrap();
+    let area = Rect::new(0, 0, 30, 8);
+    let inner = area.inner(Margin::new(1, 1));
+
+    let mut played = Deck::EMPTY;
+    played.push(card);
+    // A copy of the card played is not its own ally
+    let mut buf = Buffer::empty(area);
+    card.widget()
+        .set_played(Some(&played))
+        .render(area, &mut buf);
+    assert!(!has_modifier(&buf, inner, Modifier::BOLD));
+
+    let mut ally = card;
+    ally.id = CardId(1);
+    played.push(ally);
+    let mut buf = Buffer::empty(area);
+    played[0]
+        .widget()
+        .set_played(Some(&played))
+        .render(area, &mut buf);
+    assert!(has_modifier(&buf, inner, Modifier::BOLD));
+
+    // The border has the colour of the faction in the palette
+    let faction = card.faction.first().unwrap();
+    assert_eq!(buf[(0, 0)].fg, faction.color());
+    "colour-blind".parse::<Palette>().unwrap().set();
+    let mut buf = Buffer::empty(area);
+    card.widget().render(area, &mut buf);
+    assert!(matches!(buf[(0, 0)].fg, Color::Rgb(..)));
+    assert_eq!(buf[(0, 0)].fg, Palette::ColourBlind.faction(faction));
+}
@@ -0,0 +1,69 @@
+use stars_gapa::{
+    command::{self, Command},
+    game::Game,
+    selection::Location,
+};
+
+#[test]
+pub fn commands_play_a_game() {
+    let mut game = Game::with_seed(3);
+    let hand: Vec<_> = game.players[game.current_player]
+        .hand
+        .iter()
+        .map(|card| card.name())
+        .collect();
+    assert_eq!(hand, ["Viper", "Scout", "Scout"]);
+
+    // The indices name the cards of the current player
+    let execute = |game: &mut Game, line| command::execute(game, line);
+    assert_eq!(
+        execute(&mut game, "play 0"),
+        Ok(String::from(
+            "turn 1, 1st player playing: 0 gold, 1 attack, 50 authority, opponent 50 authority"
+        ))
+    );
+    execute(&mut game, "play all").unwrap();
+    assert_eq!(game.players[game.current_player].gold, 2);
+    assert_eq!(
+        execute(&mut game, "buy 9"),
+        Err(String::from("no card at index 9 of the shop"))
+    );
+    execute(&mut game, "buy 1").unwrap();
+    assert_eq!(
+        game.players[game.current_player].discard[0].name(),
+        "Blob Fighter"
+    );
+    assert!(
+        execute(&mut game, "buy 0")
+            .unwrap_err()
+            .contains("rejected")
+    );
+    execute(&mut game, "undo").unwrap();
+    assert!(game.players[game.current_player].discard.is_empty());
+    assert!(execute(&mut game, "show").unwrap().contains("hand: empty"));
+
+    assert_eq!(
+        "choose hand:0 discard:3".parse(),
+        Ok(Command::Choose(vec![
+            (Location::Hand, 0),
+            (Location::Discard, 3)
+        ]))
+    );
+    assert!("choose hand".parse::<Command>().is_err());
+    assert!("play 1 2".parse::<Command>().is_err());
+}
+
+#[test]
+pub fn scripts_count_their_failures() {
+    let script = "# a turn\nplay all\nbuy 1\n\nattack\nfoo\nend\n";
+    let mut game = Game::with_seed(3);
+    let mut output = Vec::new();
+    let failures = command::run(&mut game, script.as_bytes(), &mut output).unwrap();
+    let output = String::from_utf8(output).unwrap();
+    let lines: Vec<_> = output.lines().collect();
+    assert_eq!(failures, 1);
+    assert_eq!(lines.len(), 6);
+    assert_eq!(lines[0], "seed 3");
+    assert_eq!(lines[4], "error: unknown command 'foo', try 'help'");
+    assert_eq!(game.turn, 2);
+}
@@ -0,0 +1,44 @@
+//! Helpers shared by the integration tests
+#![allow(dead_code)]
+
+use rand::{RngExt, SeedableRng};
+use stars_gapa::{
+    event::Event,
+    game::{Game, GameRng},
+    theme::{Palette, Theme},
+};
+use std::sync::{Mutex, MutexGuard, PoisonError};
+
+/// Lock of the global [Theme] and [Palette]
+static THEME: Mutex<()> = Mutex::new(());
+
+/// Sets the global [Theme] and [Palette] for as long as the returned guard lives
+///
+/// The tests of a file run in parallel threads, the ones displaying or rendering
+/// with another theme or palette than the default ones hold this lock.
+pub fn theme_lock(theme: Theme, palette: Palette) -> MutexGuard<'static, ()> {
+    let guard = THEME.lock().unwrap_or_else(PoisonError::into_inner);
+    theme.set();
+    palette.set();
+    guard
+}
+
+/// A game of `nb` random legal moves from a `seed`,
+/// one move in `noise` is a random event, maybe rejected, unless `noise` is 0
+pub fn random_game(seed: u64, nb: usize, noise: u32) -> Game {
+    let mut game = Game::with_seed(seed);
+    let mut rng = GameRng::seed_from_u64(seed);
+    for _ in 0..nb {
+        let legal = game.legal_events();
+        if legal.is_empty() {
+            break;
+        }
+        let event = if noise != 0 && rng.random_range(0..noise) == 0 {
+            Event::random(&mut rng)
+        } else {
+            legal[rng.random_range(0..legal.len())].clone()
+        };
+        let _ = game.apply_event(event);
+    }
+    game
+}
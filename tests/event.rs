@@ -0,0 +1,84 @@
+use stars_gapa::{
+    card::CardId,
+    event::{Event, Rejected},
+    game::Game,
+    invariants,
+    selection::Location,
+    state::State,
+};
+
+#[test]
+pub fn events_are_written_on_a_line() {
+    let events = [
+        Event::Buy(CardId(2)),
+        Event::BuyExplorer,
+        Event::Play(CardId(40)),
+        Event::PlayAll,
+        Event::Activate(CardId(7)),
+        Event::Choose(vec![CardId(41), CardId(12)]),
+        Event::Choose(vec![]),
+        Event::Attack,
+        Event::NextTurn,
+    ];
+    for event in events {
+        assert_eq!(event.to_string().parse(), Ok(event));
+    }
+    assert_eq!(
+        Event::Choose(vec![CardId(41), CardId(12)]).to_string(),
+        "choose #41 #12"
+    );
+    assert!("play 0".parse::<Event>().is_err());
+    assert!("buy #2 #3".parse::<Event>().is_err());
+}
+
+#[test]
+pub fn events_name_the_cards_by_id() {
+    let mut game = Game::with_seed(2);
+    let player = game.current_player;
+    let hand: Vec<_> = game.players[player]
+        .hand
+        .iter()
+        .map(|card| card.id)
+        .collect();
+
+    // The last card of the hand is found after the first one is played
+    game.apply_event(Event::Play(hand[0])).unwrap();
+    game.apply_event(Event::Play(hand[hand.len() - 1])).unwrap();
+    assert_eq!(
+        game.apply_event(Event::Play(hand[0])),
+        Err(Rejected::NoSuchCard(hand[0]))
+    );
+    let played: Vec<_> = game.players[player]
+        .played
+        .iter()
+        .map(|card| card.id)
+        .collect();
+    assert_eq!(played, [hand[0], hand[hand.len() - 1]]);
+
+    // The bought explorers are new cards
+    game.players[player].gold = 4;
+    game.apply_event(Event::BuyExplorer).unwrap();
+    game.apply_event(Event::BuyExplorer).unwrap();
+    let position = game
+        .position_of(game.players[player].discard[1].id)
+        .unwrap();
+    assert_eq!(
+        (position.player, position.loc, position.index),
+        (player, Location::Discard, 1)
+    );
+    invariants::check(&game).unwrap();
+}
+
+#[test]
+pub fn activating_a_card_is_rejected() {
+    let mut game = Game::with_seed(2);
+    let player = game.current_player;
+    let card = game.players[player].hand[0].id;
+    game.apply_event(Event::Play(card)).unwrap();
+    assert!(matches!(game.state, State::Playing));
+    assert_eq!(
+        game.apply_event(Event::Activate(card)),
+        Err(Rejected::Unsupported)
+    );
+    assert!(!game.legal_events().contains(&Event::Activate(card)));
+}
@@ -0,0 +1,86 @@
+#![cfg(feature = "tui")]
+
+use ratatui::{buffer::Buffer, layout::Rect, widgets::Widget};
+use stars_gapa::{
+    action::ActionLoc, ai, event::Event, game::Game, input::Input, state::State, tui::view::View,
+};
+
+fn text(game: &Game, view: &View) -> String {
+    let area = Rect::new(0, 0, 160, 50);
+    let mut buf = Buffer::empty(area);
+    game.widget(view).render(area, &mut buf);
+    buf.content()
+        .iter()
+        .map(|cell| cell.symbol())
+        .collect::<String>()
+}
+
+#[test]
+pub fn hints_are_legal_until_the_game_is_won() {
+    for seed in 0..5 {
+        let mut game = Game::with_seed(seed);
+        while game.turn < 40 {
+            let Some(hint) = ai::hint(&game) else {
+                assert!(matches!(game.state, State::WonBy(_)));
+                break;
+            };
+            assert!(
+                game.legal_events().contains(&hint.event),
+                "{} is not legal",
+                hint.event
+            );
+            game.apply_event(hint.event).unwrap();
+        }
+    }
+}
+
+#[test]
+pub fn hint_key_highlights_the_move() {
+    let mut game = Game::with_seed(3);
+    let mut view = View::default();
+    assert!(!text(&game, &view).contains("[hint]"));
+    view.interact(&mut game, Input::Hint);
+    let hint = view.hint.clone().unwrap();
+    let card = game[game.current_player].hand[0];
+    assert_eq!(hint.event, Event::Play(card.id));
+    assert_eq!(hint.card, Some(card.id));
+    let shown = text(&game, &view);
+    assert!(shown.contains(&format!("[hint] Play {}", card.name())));
+    // The card to play is titled on the board
+    assert!(shown.contains("─hint─"));
+
+    // The hint is forgotten once an event is played
+    view.interact(&mut game, Input::PlayAll);
+    assert_eq!(view.hint, None);
+    assert!(!text(&game, &view).contains("[hint]"));
+}
+
+#[test]
+pub fn hints_choose_among_the_legal_cards() {
+    // The cheapest card of the hand is discarded
+    let mut game = Game::with_seed(3);
+    let player = game.current_player;
+    game.state = State::Discarding {
+        nb: 1,
+        loc: ActionLoc::Hand,
+    };
+    let cheapest = *game[player]
+        .hand
+        .iter()
+        .min_by_key(|card| card.gold)
+        .unwrap();
+    let hint = ai::hint(&game).unwrap();
+    assert_eq!(hint.event, Event::Choose(vec![cheapest.id]));
+    game.apply_event(hint.event).unwrap();
+    assert_eq!(game[player].discard.position(cheapest.id), Some(0));
+
+    // The only played card is copied
+    let mut game = Game::with_seed(3);
+    let player = game.current_player;
+    let card = game[player].hand[0].id;
+    game.apply_event(Event::Play(card)).unwrap();
+    game.state = State::Copy;
+    let hint = ai::hint(&game).unwrap();
+    assert_eq!(hint.event, Event::Choose(vec![card]));
+    assert!(game.legal_events().contains(&hint.event));
+}
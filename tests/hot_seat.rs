@@ -0,0 +1,24 @@
+#![cfg(feature = "tui")]
+
+use stars_gapa::{game::Game, hot_seat::HotSeat, input::Input, selection::GamePosition};
+
+#[test]
+pub fn hands_hidden_between_turns() {
+    let mut hot_seat = HotSeat::new(Game::with_seed(7));
+    let first = hot_seat.game.current_player;
+    assert_eq!(hot_seat.waiting, Some(first));
+
+    // Nothing is played before the player confirms
+    hot_seat.interact(Input::Down);
+    assert_eq!(hot_seat.view.position, GamePosition::default());
+    hot_seat.interact(Input::Enter);
+    assert_eq!(hot_seat.waiting, None);
+
+    // Ending the turn hides the hands until the next player confirms
+    hot_seat.interact(Input::Enter);
+    assert_eq!(hot_seat.game.current_player, first.other());
+    assert_eq!(hot_seat.waiting, Some(first.other()));
+    hot_seat.interact(Input::Enter);
+    assert_eq!(hot_seat.waiting, None);
+    assert_eq!(hot_seat.game.current_player, first.other());
+}
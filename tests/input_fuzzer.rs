@@ -1,16 +1,46 @@
-use stars_gapa::{event::Event, game::Game, input::Input};
+#[cfg(feature = "tui")]
+use stars_gapa::tui::view::Gesture;
+use stars_gapa::{
+    event::Event,
+    fuzz::{Case, Step, fuzz},
+};
+use std::path::PathBuf;
 
-#[test]
-pub fn input_fuzzer() {
-    let nb_thread = 16;
-    let nb_event = 200000;
+/// Shrunk failing cases, replayed by [regressions]
+fn regressions_dir() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/regressions")
+}
+
+/// Seed of the long runs, `STARS_GAPA_FUZZ_SEED=n` or a random one
+fn long_run_seed() -> u64 {
+    match std::env::var("STARS_GAPA_FUZZ_SEED") {
+        Ok(seed) => seed.parse().expect("STARS_GAPA_FUZZ_SEED is a number"),
+        Err(_) => rand::random(),
+    }
+}
 
+/// Fuzzes with `nb_thread` threads seeded with `seed`, `seed + 1`...
+///
+/// A failure is shrunk and written to a regression file.
+fn fuzz_threads<S: Step + Send + 'static>(
+    kind: &'static str,
+    seed: u64,
+    nb_thread: u64,
+    nb_step: usize,
+) {
     let handles: Vec<_> = (0..nb_thread)
-        .map(|_| {
+        .map(|i| {
+            let seed = seed.wrapping_add(i);
             std::thread::spawn(move || {
-                let mut game = Game::new();
-                for _ in 0..nb_event {
-                    game.interact(Input::random());
+                if let Err((case, failure)) = fuzz::<S>(seed, nb_step) {
+                    let path = regressions_dir().join(format!("{kind}-{seed}.txt"));
+                    std::fs::create_dir_all(regressions_dir()).unwrap();
+                    std::fs::write(&path, format!("# {failure}\n{case}")).unwrap();
+                    panic!(
+                        "STARS_GAPA_FUZZ_SEED={seed} fails at {failure}, shrunk to {} steps in {}",
+                        case.steps.len(),
+                        path.display()
+                    );
                 }
             })
         })
@@ -21,23 +51,105 @@ pub fn input_fuzzer() {
     }
 }
 
+#[cfg(feature = "tui")]
+#[test]
+pub fn input_fuzzer() {
+    fuzz_threads::<Gesture>("input", 0, 4, 2000);
+}
+
 #[test]
 pub fn event_fuzzer() {
-    let nb_thread = 16;
-    let nb_event = 200000;
+    fuzz_threads::<Event>("event", 0, 4, 2000);
+}
 
-    let handles: Vec<_> = (0..nb_thread)
-        .map(|_| {
-            std::thread::spawn(move || {
-                let mut game = Game::new();
-                for _ in 0..nb_event {
-                    game.apply_event(Event::random());
-                }
-            })
-        })
-        .collect();
+/// `cargo test --test input_fuzzer -- --ignored`
+#[cfg(feature = "tui")]
+#[test]
+#[ignore = "long run, seeded by STARS_GAPA_FUZZ_SEED"]
+pub fn input_fuzzer_long_run() {
+    fuzz_threads::<Gesture>("input", long_run_seed(), 16, 200000);
+}
 
-    for handle in handles {
-        handle.join().expect("This thread has crashed");
+#[test]
+#[ignore = "long run, seeded by STARS_GAPA_FUZZ_SEED"]
+pub fn event_fuzzer_long_run() {
+    fuzz_threads::<Event>("event", long_run_seed(), 16, 200000);
+}
+
+/// Replays the failures found by the fuzzers
+#[test]
+pub fn regressions() {
+    let Ok(files) = std::fs::read_dir(regressions_dir()) else {
+        return;
+    };
+    for path in files.map(|file| file.unwrap().path()) {
+        let text = std::fs::read_to_string(&path).unwrap();
+        let name = path.file_name().unwrap().to_string_lossy();
+        let result = if name.starts_with("input-") {
+            #[cfg(not(feature = "tui"))]
+            continue;
+            #[cfg(feature = "tui")]
+            text.parse::<Case<Gesture>>().map(|case| case.run())
+        } else {
+            text.parse::<Case<Event>>().map(|case| case.run())
+        };
+        match result {
+            Ok(Ok(())) => (),
+            Ok(Err(failure)) => panic!("{name} still fails at {failure}"),
+            Err(error) => panic!("{name} cannot be read: {error}"),
+        }
     }
 }
+
+/// An [Event] or a step failing from the third turn
+#[derive(Debug, Clone, PartialEq)]
+enum Boom {
+    Event(Event),
+    Boom,
+}
+
+impl std::fmt::Display for Boom {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Boom::Event(event) => write!(f, "{event}"),
+            Boom::Boom => write!(f, "boom"),
+        }
+    }
+}
+
+impl std::str::FromStr for Boom {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "boom" => Ok(Boom::Boom),
+            _ => s.parse().map(Boom::Event),
+        }
+    }
+}
+
+impl Step for Boom {
+    type Target = stars_gapa::game::Game;
+    fn random(rng: &mut stars_gapa::game::GameRng) -> Self {
+        Boom::Event(Event::random(rng))
+    }
+    fn apply(&self, game: &mut stars_gapa::game::Game) {
+        match self {
+            Boom::Event(event) => event.apply(game),
+            Boom::Boom => assert!(game.turn < 3, "boom"),
+        }
+    }
+}
+
+#[test]
+pub fn shrinking() {
+    let mut case = Case::<Boom>::generate(5, 300);
+    case.steps.push(Boom::Boom);
+    let failure = case.run().unwrap_err();
+    assert!(failure.error.contains("boom"), "{failure}");
+
+    let shrunk = case.shrink();
+    assert_eq!(format!("{shrunk}"), "seed 5\nend\nend\nboom\n");
+    assert_eq!(shrunk.to_string().parse(), Ok(shrunk.clone()));
+    assert_eq!(shrunk.run().unwrap_err().step, 2);
+}
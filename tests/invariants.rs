@@ -0,0 +1,78 @@
+use stars_gapa::{
+    action::ActionLoc,
+    deck::Deck,
+    event::{Event, Rejected},
+    game::Game,
+    invariants,
+    state::State,
+};
+
+#[test]
+pub fn rules_keep_the_invariants() {
+    // An attack equal to the authority of the opponent wins
+    let mut game = Game::with_seed(1);
+    let player = game.current_player;
+    game.players[player].attack = 50;
+    game.apply_event(Event::Attack).unwrap();
+    assert!(matches!(game.state, State::WonBy(winner) if winner == player));
+    invariants::check(&game).unwrap();
+
+    // The scrapped cards go to the scrap heap
+    let mut game = Game::with_seed(1);
+    let player = game.current_player;
+    game.state = State::Scraping {
+        nb: 2,
+        loc: ActionLoc::Hand,
+    };
+    let card: Vec<_> = game.players[player]
+        .hand
+        .iter()
+        .map(|card| card.id)
+        .collect();
+    let hand = game.players[player].hand.len();
+    let opponent = game.players[player.other()].draw_pile[0].id;
+    assert_eq!(
+        game.apply_event(Event::Choose(vec![opponent])),
+        Err(Rejected::WrongChoice)
+    );
+    game.apply_event(Event::Choose(vec![card[0], card[1]]))
+        .unwrap();
+    assert_eq!(game.players[player].hand.len(), hand - 2);
+    assert_eq!(game.scrap_heap.len(), 2);
+    invariants::check(&game).unwrap();
+
+    // The discarded cards go from the hand to the discard pile
+    let mut game = Game::with_seed(1);
+    let player = game.current_player;
+    game.state = State::Discarding {
+        nb: 1,
+        loc: ActionLoc::Hand,
+    };
+    let card = game.players[player].hand[0].id;
+    let opponent = game.players[player.other()].draw_pile[0].id;
+    assert_eq!(
+        game.apply_event(Event::Choose(vec![opponent])),
+        Err(Rejected::NoSuchCard(opponent))
+    );
+    game.apply_event(Event::Choose(vec![card])).unwrap();
+    assert_eq!(game.players[player].hand.position(card), None);
+    assert_eq!(game.players[player].discard.position(card), Some(0));
+    invariants::check(&game).unwrap();
+
+    // Once the deck is empty, the bought cards are not replaced in the shop
+    let mut game = Game::with_seed(1);
+    let player = game.current_player;
+    let mut deck = std::mem::replace(&mut game.deck, Deck::EMPTY);
+    while !deck.is_empty() {
+        game.players[player].draw_pile.push(deck.remove_last());
+    }
+    game.players[player].gold = 100;
+    game.apply_event(Event::Buy(game.shop[0].id)).unwrap();
+    assert_eq!(game.shop.len(), 4);
+    assert_eq!(game.players[player].discard.len(), 1);
+    invariants::check(&game).unwrap();
+
+    // A lost card is found
+    game.players[player].discard.remove_last();
+    assert!(invariants::check(&game).is_err());
+}
@@ -0,0 +1,89 @@
+#![cfg(feature = "tui")]
+
+use ratatui::{
+    buffer::Buffer,
+    layout::{Position, Rect},
+    widgets::Widget,
+};
+use stars_gapa::{
+    event::Event,
+    game::Game,
+    selection::Location,
+    tui::{
+        game::{GameLayout, LayoutMode},
+        view::{Mouse, View},
+    },
+};
+
+fn render(game: &Game, view: &View, width: u16, height: u16) -> Buffer {
+    let area = Rect::new(0, 0, width, height);
+    let mut buf = Buffer::empty(area);
+    game.widget(view).render(area, &mut buf);
+    buf
+}
+
+fn text(buf: &Buffer) -> String {
+    buf.content.iter().map(|cell| cell.symbol()).collect()
+}
+
+#[test]
+pub fn layout_modes() {
+    assert_eq!(
+        LayoutMode::of(Rect::new(0, 0, 50, 15)),
+        LayoutMode::TooSmall
+    );
+    assert_eq!(LayoutMode::of(Rect::new(0, 0, 80, 24)), LayoutMode::Compact);
+    assert_eq!(LayoutMode::of(Rect::new(0, 0, 160, 50)), LayoutMode::Normal);
+    assert_eq!(LayoutMode::of(Rect::new(0, 0, 220, 60)), LayoutMode::Wide);
+
+    let game = Game::with_seed(7);
+    let mut view = View::default();
+    assert!(text(&render(&game, &view, 50, 15)).contains("too small"));
+    assert_eq!(
+        view.get_mouse_event(
+            &game,
+            Mouse::Click {
+                column: 10,
+                row: 10
+            }
+        ),
+        None
+    );
+
+    // The wide cards describe their actions with sentences
+    assert!(text(&render(&game, &view, 220, 60)).contains("Gain 1 gold."));
+    assert!(!text(&render(&game, &view, 160, 50)).contains("Gain 1 gold."));
+}
+
+#[test]
+pub fn compact_lists() {
+    let game = Game::with_seed(7);
+    let mut view = View::default();
+    render(&game, &view, 80, 24);
+    let layout = GameLayout::new(Rect::new(0, 0, 80, 24));
+
+    // Each card of the hand is a line
+    let hand = layout.hand[0];
+    let len = game.players[game.current_player].hand.len();
+    let lines = layout.card_areas(len, hand);
+    assert_eq!(lines.len(), len);
+    assert!(lines.iter().all(|(_, line)| line.height == 1));
+    let (index, line) = lines[len - 1];
+    let click = Mouse::Click {
+        column: line.x,
+        row: line.y,
+    };
+    let id = game.players[game.current_player].hand[index].id;
+    assert_eq!(view.get_mouse_event(&game, click), Some(Event::Play(id)));
+
+    // The popup of the draw pile leaves the side column visible
+    view.position.loc = Location::DrawPile;
+    view.position.player = game.current_player;
+    let buf = render(&game, &view, 80, 24);
+    let info = layout.info[0];
+    assert!(!layout.popup.intersects(info));
+    let info_line: String = (info.x..info.right())
+        .map(|x| buf[Position::new(x, info.y)].symbol())
+        .collect();
+    assert!(info_line.contains("50"), "{info_line}");
+}
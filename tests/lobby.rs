@@ -0,0 +1,514 @@
+use stars_gapa::{
+    card::{Card, CardId},
+    event::Event,
+    game::{Game, Viewer},
+    log::{LogEntry, Logged},
+    network::{
+        client::{Client, Mirror},
+        message::{ClientMessage, ServerMessage, TableInfo},
+        server::Server,
+    },
+    player_id::PlayerId,
+};
+use std::{
+    net::SocketAddr,
+    time::{Duration, Instant},
+};
+
+fn start_server() -> SocketAddr {
+    start_server_with(Server::bind("127.0.0.1:0").expect("Cannot bind the server"))
+}
+
+fn start_server_with(server: Server) -> SocketAddr {
+    let addr = server.local_addr().unwrap();
+    server.spawn();
+    addr
+}
+
+fn recv_any(client: &Client) -> ServerMessage {
+    client
+        .recv_timeout(Duration::from_secs(5))
+        .expect("The server did not answer")
+}
+
+/// Receives the next message that is not an entry of the log
+fn recv(client: &Client) -> ServerMessage {
+    loop {
+        match recv_any(client) {
+            ServerMessage::Logged(_) => (),
+            message => return message,
+        }
+    }
+}
+
+fn list(client: &mut Client) -> Vec<TableInfo> {
+    client.send(&ClientMessage::List).unwrap();
+    match recv(client) {
+        ServerMessage::Tables(tables) => tables,
+        message => panic!("Expected the tables, got {message:?}"),
+    }
+}
+
+fn create(client: &mut Client, name: &str) -> usize {
+    client.send(&ClientMessage::Create(name.into())).unwrap();
+    match recv(client) {
+        ServerMessage::Created(table) => table,
+        message => panic!("Expected a created table, got {message:?}"),
+    }
+}
+
+/// Returns the token of the seat
+fn join(client: &mut Client, table: usize, seat: PlayerId) -> u64 {
+    client.send(&ClientMessage::Join { table, seat }).unwrap();
+    match recv(client) {
+        ServerMessage::Joined {
+            table: joined,
+            seat: sat,
+            token,
+        } if joined == table && sat == seat => token,
+        message => panic!("Expected to join the table, got {message:?}"),
+    }
+}
+
+/// Receives the game as the client may see it
+fn position(client: &Client) -> Game {
+    match recv(client) {
+        ServerMessage::Position { checksum, code } => {
+            let game = Game::from_position_code(&code).unwrap();
+            assert_eq!(game.state_hash(), checksum);
+            game
+        }
+        message => panic!("Expected a position, got {message:?}"),
+    }
+}
+
+/// Receives an accepted event and the position after it
+fn played(client: &Client) -> (Event, Game) {
+    match recv(client) {
+        ServerMessage::Played(event) => (event, position(client)),
+        message => panic!("Expected an event, got {message:?}"),
+    }
+}
+
+fn is_hidden(cards: &stars_gapa::deck::Deck) -> bool {
+    cards.iter().all(Card::is_hidden)
+}
+
+#[test]
+pub fn messages_round_trip() {
+    let messages = [
+        ClientMessage::List,
+        ClientMessage::Create("my table".into()),
+        ClientMessage::Join {
+            table: 3,
+            seat: PlayerId::Second,
+        },
+        ClientMessage::Spectate { table: 0, delay: 0 },
+        ClientMessage::Spectate {
+            table: 1,
+            delay: 30,
+        },
+        ClientMessage::Reconnect { token: 1234 },
+        ClientMessage::Resync,
+        ClientMessage::Leave,
+        ClientMessage::Play(Event::Buy(CardId(2))),
+        ClientMessage::Play(Event::Choose(vec![CardId(40), CardId(3)])),
+    ];
+    for message in messages {
+        let line = format!("{message}");
+        let parsed: ClientMessage = line.parse().unwrap();
+        // Table names are a single word in the protocol
+        assert_eq!(format!("{parsed}"), line);
+    }
+
+    let messages = [
+        ServerMessage::Tables(vec![
+            TableInfo {
+                id: 0,
+                name: "alpha".into(),
+                free: vec![PlayerId::Second],
+            },
+            TableInfo {
+                id: 1,
+                name: "beta".into(),
+                free: vec![],
+            },
+        ]),
+        ServerMessage::Created(1),
+        ServerMessage::Watching {
+            table: 1,
+            delay: 30,
+        },
+        ServerMessage::Played(Event::NextTurn),
+        ServerMessage::Joined {
+            table: 1,
+            seat: PlayerId::First,
+            token: 1234,
+        },
+        ServerMessage::Position {
+            checksum: 99,
+            code: Game::with_seed(42)
+                .seen_by(Viewer::Spectator)
+                .position_code()
+                .to_string(),
+        },
+        ServerMessage::Logged(LogEntry {
+            turn: 3,
+            player: PlayerId::Second,
+            logged: Logged::Bought {
+                card: "Blob Fighter",
+                gold: 1,
+            },
+        }),
+        ServerMessage::Logged(LogEntry {
+            turn: 4,
+            player: PlayerId::First,
+            logged: Logged::Attacked {
+                attack: 5,
+                authority: 45,
+            },
+        }),
+        ServerMessage::Closed,
+        ServerMessage::Error("it is not your turn".into()),
+    ];
+    for message in messages {
+        assert_eq!(format!("{message}").parse::<ServerMessage>(), Ok(message));
+    }
+}
+
+#[test]
+pub fn lobby_with_concurrent_tables() {
+    let addr = start_server();
+    let mut clients: Vec<Client> = (0..4).map(|_| Client::connect(addr).unwrap()).collect();
+
+    let alpha = create(&mut clients[0], "alpha");
+    let beta = create(&mut clients[2], "beta");
+    join(&mut clients[0], alpha, PlayerId::First);
+    join(&mut clients[2], beta, PlayerId::Second);
+
+    // Both tables are listed with their free seat
+    let tables = list(&mut clients[1]);
+    assert_eq!(tables.len(), 2);
+    assert!(
+        tables
+            .iter()
+            .any(|table| table.id == alpha && table.free == vec![PlayerId::Second])
+    );
+
+    // A taken seat cannot be joined
+    clients[1]
+        .send(&ClientMessage::Join {
+            table: alpha,
+            seat: PlayerId::First,
+        })
+        .unwrap();
+    assert!(matches!(recv(&clients[1]), ServerMessage::Error(_)));
+
+    join(&mut clients[1], alpha, PlayerId::Second);
+    join(&mut clients[3], beta, PlayerId::First);
+
+    // Every client receives the game of its table as its seat sees it
+    let mut games: Vec<Game> = clients.iter().map(position).collect();
+    let seats = [
+        PlayerId::First,
+        PlayerId::Second,
+        PlayerId::Second,
+        PlayerId::First,
+    ];
+    for (game, seat) in games.iter().zip(seats) {
+        // The seed stays on the server
+        assert_eq!(game.seed, 0);
+        assert!(is_hidden(&game.deck));
+        assert!(is_hidden(&game.players[seat.other()].hand));
+        assert!(is_hidden(&game.players[seat.other()].draw_pile));
+        assert!(!game.players[seat].draw_pile.iter().any(Card::is_hidden));
+    }
+    assert_eq!(games[0].current_player, games[1].current_player);
+    let playing = games[0].current_player;
+    assert_eq!(games[playing.to_usize()].players[playing].hand.len(), 3);
+    assert!(
+        !games[playing.to_usize()].players[playing]
+            .hand
+            .iter()
+            .any(Card::is_hidden)
+    );
+
+    // The client that is not playing cannot play
+    let waiting = if games[0].current_player == seats[0] {
+        1
+    } else {
+        0
+    };
+    clients[waiting]
+        .send(&ClientMessage::Play(Event::NextTurn))
+        .unwrap();
+    assert!(matches!(recv(&clients[waiting]), ServerMessage::Error(_)));
+
+    // Play a few turns on both tables at the same time
+    for _ in 0..6 {
+        for table in [0, 2] {
+            let playing = if games[table].current_player == seats[table] {
+                table
+            } else {
+                table + 1
+            };
+            clients[playing]
+                .send(&ClientMessage::Play(Event::NextTurn))
+                .unwrap();
+            for i in [table, table + 1] {
+                let (event, game) = played(&clients[i]);
+                assert_eq!(event, Event::NextTurn);
+                // The cards drawn by the opponent stay hidden
+                assert!(is_hidden(&game.players[seats[i].other()].hand));
+                games[i] = game;
+            }
+            assert_eq!(games[table].turn, games[table + 1].turn);
+            assert_eq!(games[table].current_player, games[table + 1].current_player);
+        }
+    }
+
+    // A table is closed and removed from the lobby when a player leaves
+    clients[3].send(&ClientMessage::Leave).unwrap();
+    assert_eq!(recv(&clients[2]), ServerMessage::Closed);
+    let tables = list(&mut clients[3]);
+    assert_eq!(tables.len(), 1);
+    assert_eq!(tables[0].id, alpha);
+    assert!(tables[0].free.is_empty());
+
+    clients[1].send(&ClientMessage::Leave).unwrap();
+    assert_eq!(recv(&clients[0]), ServerMessage::Closed);
+    assert!(list(&mut clients[0]).is_empty());
+}
+
+#[test]
+pub fn spectators_watch_without_playing() {
+    let addr = start_server();
+    let mut players: Vec<Client> = (0..2).map(|_| Client::connect(addr).unwrap()).collect();
+    let table = create(&mut players[0], "casted");
+    join(&mut players[0], table, PlayerId::First);
+    join(&mut players[1], table, PlayerId::Second);
+    let game = position(&players[0]);
+    position(&players[1]);
+    let playing = game.current_player.to_usize();
+
+    // A first event is played before the spectators arrive
+    players[playing]
+        .send(&ClientMessage::Play(Event::NextTurn))
+        .unwrap();
+    for player in &players {
+        assert_eq!(played(player).0, Event::NextTurn);
+    }
+
+    let mut live = Client::connect(addr).unwrap();
+    live.send(&ClientMessage::Spectate { table, delay: 0 })
+        .unwrap();
+    assert_eq!(recv(&live), ServerMessage::Watching { table, delay: 0 });
+    // The spectator receives the game as it is, without the hands
+    let watched = position(&live);
+    assert_eq!(watched.turn, 2);
+    assert!(watched.players.iter().all(|player| is_hidden(&player.hand)));
+    assert!(matches!(
+        recv_any(&live),
+        ServerMessage::Logged(LogEntry {
+            logged: Logged::EndedTurn,
+            ..
+        })
+    ));
+
+    let mut delayed = Client::connect(addr).unwrap();
+    delayed
+        .send(&ClientMessage::Spectate { table, delay: 1 })
+        .unwrap();
+    assert_eq!(recv(&delayed), ServerMessage::Watching { table, delay: 1 });
+
+    // Spectators move their cursor on the game they see, but cannot play
+    #[cfg(feature = "tui")]
+    {
+        let mut view = stars_gapa::tui::view::View::default();
+        let explorer = view.position.clone();
+        view.get_event(&watched, stars_gapa::input::Input::Right);
+        assert_ne!(view.position, explorer);
+    }
+    live.send(&ClientMessage::Play(Event::NextTurn)).unwrap();
+    assert!(matches!(recv(&live), ServerMessage::Error(_)));
+
+    // The next events reach the players and the live spectator at once
+    let sent = Instant::now();
+    players[1 - playing]
+        .send(&ClientMessage::Play(Event::NextTurn))
+        .unwrap();
+    let (_, watched) = played(&live);
+    // Two turns later the first player is playing again
+    assert_eq!(watched.current_player, game.current_player);
+
+    // The delayed spectator receives the whole game late, with the hands
+    let start = position(&delayed);
+    assert_eq!(start.turn, 1);
+    assert_eq!(start.players[game.current_player].hand.len(), 3);
+    assert!(
+        !start.players[game.current_player]
+            .hand
+            .iter()
+            .any(Card::is_hidden)
+    );
+    assert!(is_hidden(&start.deck));
+    assert_eq!(played(&delayed).1.turn, 2);
+    assert_eq!(played(&delayed).1.turn, 3);
+    assert!(sent.elapsed() >= Duration::from_millis(900));
+
+    // A player leaving ends the game, spectators do not keep a table open
+    players[0].send(&ClientMessage::Leave).unwrap();
+    assert_eq!(recv(&live), ServerMessage::Closed);
+    assert!(list(&mut live).is_empty());
+    // The delayed spectator sees the end of the game
+    assert_eq!(recv(&delayed), ServerMessage::Closed);
+}
+
+/// Updates a mirror with the messages of the server until `done`
+fn mirror_until(client: &mut Client, mirror: &mut Mirror, done: impl Fn(&ServerMessage) -> bool) {
+    loop {
+        let message = recv_any(client);
+        let is_done = done(&message);
+        if let Some(answer) = mirror.update(message) {
+            client.send(&answer).unwrap();
+        }
+        if is_done {
+            return;
+        }
+    }
+}
+
+#[test]
+pub fn reconnection_and_resync() {
+    let addr = start_server();
+    let mut clients: Vec<Client> = (0..2).map(|_| Client::connect(addr).unwrap()).collect();
+    let mut mirrors = [Mirror::default(), Mirror::default()];
+    let table = create(&mut clients[0], "flaky");
+    for (i, seat) in [PlayerId::First, PlayerId::Second].into_iter().enumerate() {
+        clients[i]
+            .send(&ClientMessage::Join { table, seat })
+            .unwrap();
+        mirror_until(&mut clients[i], &mut mirrors[i], |message| {
+            matches!(message, ServerMessage::Joined { .. })
+        });
+    }
+    for i in 0..2 {
+        mirror_until(&mut clients[i], &mut mirrors[i], |message| {
+            matches!(message, ServerMessage::Position { .. })
+        });
+    }
+    let playing = mirrors[0].game.as_ref().unwrap().current_player.to_usize();
+
+    // The game continues after a lost connection
+    let token = mirrors[playing].token.unwrap();
+    clients[playing] = Client::connect(addr).unwrap();
+    mirrors[playing].game = None;
+    std::thread::sleep(Duration::from_millis(100));
+    let tables = list(&mut clients[playing]);
+    assert_eq!(tables.len(), 1);
+    assert!(tables[0].free.is_empty());
+
+    // The player sits back with its token in the background and receives the game
+    let reconnecting = Client::reconnect(&addr.to_string(), token);
+    assert!(reconnecting.try_recv().is_err());
+    clients[playing] = reconnecting
+        .recv_timeout(Duration::from_secs(5))
+        .expect("The client did not reconnect")
+        .unwrap();
+    mirror_until(&mut clients[playing], &mut mirrors[playing], |message| {
+        matches!(message, ServerMessage::Position { .. })
+    });
+    assert!(mirrors[playing].game.is_some());
+
+    // A refused token stops the reconnection
+    let refused = Client::reconnect(&addr.to_string(), token.wrapping_add(1))
+        .recv_timeout(Duration::from_secs(5))
+        .expect("The reconnection did not stop");
+    assert!(refused.is_err());
+
+    // A position differing from its checksum is sent again
+    let mirror = &mut mirrors[1 - playing];
+    let code = mirror.game.as_ref().unwrap().position_code().to_string();
+    let corrupted = ServerMessage::Position {
+        checksum: 1,
+        code: code.clone(),
+    };
+    assert_eq!(mirror.update(corrupted), Some(ClientMessage::Resync));
+    clients[1 - playing].send(&ClientMessage::Resync).unwrap();
+    mirror_until(&mut clients[1 - playing], mirror, |message| {
+        matches!(message, ServerMessage::Position { .. })
+    });
+    assert_eq!(
+        mirror.game.as_ref().unwrap().position_code().to_string(),
+        code
+    );
+
+    // Both mirrors follow the game and its log
+    clients[playing]
+        .send(&ClientMessage::Play(Event::NextTurn))
+        .unwrap();
+    for i in 0..2 {
+        mirror_until(&mut clients[i], &mut mirrors[i], |message| {
+            matches!(message, ServerMessage::Position { .. })
+        });
+    }
+    let [first, second] = &mirrors;
+    let (first, second) = (first.game.as_ref().unwrap(), second.game.as_ref().unwrap());
+    assert_eq!(first.turn, 2);
+    assert_eq!(first.current_player, second.current_player);
+    assert_eq!(first.players[0].authority, 50);
+    assert_eq!(first.log.len(), 1);
+    assert_eq!(first.log, second.log);
+
+    // A client replaced by a reconnection with its token cannot play anymore
+    let mut replacing = Client::connect(addr).unwrap();
+    let token = mirrors[1 - playing].token.unwrap();
+    replacing.send(&ClientMessage::Reconnect { token }).unwrap();
+    assert!(matches!(recv(&replacing), ServerMessage::Joined { .. }));
+    clients[1 - playing]
+        .send(&ClientMessage::Play(Event::NextTurn))
+        .unwrap();
+    assert_eq!(
+        recv(&clients[1 - playing]),
+        ServerMessage::Error("you are not seated".into())
+    );
+}
+
+#[test]
+pub fn abandoned_tables_are_closed() {
+    let timeout = Duration::from_millis(500);
+    let server = Server::bind("127.0.0.1:0").unwrap();
+    let addr = start_server_with(server.abandoned_after(timeout));
+    let mut clients: Vec<Client> = (0..2).map(|_| Client::connect(addr).unwrap()).collect();
+    let table = create(&mut clients[0], "abandoned");
+    let tokens: Vec<u64> = [PlayerId::First, PlayerId::Second]
+        .into_iter()
+        .enumerate()
+        .map(|(i, seat)| join(&mut clients[i], table, seat))
+        .collect();
+    for client in &clients {
+        position(client);
+    }
+
+    // Both players lose their connection, the game waits for them
+    drop(clients);
+    let mut lobby = Client::connect(addr).unwrap();
+    std::thread::sleep(timeout / 5);
+    assert_eq!(list(&mut lobby).len(), 1);
+    let mut client = Client::connect(addr).unwrap();
+    client
+        .send(&ClientMessage::Reconnect { token: tokens[1] })
+        .unwrap();
+    assert!(matches!(recv(&client), ServerMessage::Joined { .. }));
+    position(&client);
+
+    // Until they are gone for too long, like a table nobody joins
+    drop(client);
+    create(&mut lobby, "empty");
+    std::thread::sleep(timeout * 2);
+    assert!(list(&mut lobby).is_empty());
+    lobby
+        .send(&ClientMessage::Reconnect { token: tokens[0] })
+        .unwrap();
+    assert!(matches!(recv(&lobby), ServerMessage::Error(_)));
+}
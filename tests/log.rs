@@ -0,0 +1,95 @@
+use stars_gapa::{
+    event::{Event, Rejected},
+    game::Game,
+    log::{LogEntry, Logged},
+};
+
+#[test]
+pub fn accepted_events_are_logged_with_their_cards() {
+    let mut game = Game::with_seed(3);
+    let player = game.current_player;
+    let names: Vec<_> = game[player].hand.iter().map(|card| card.name()).collect();
+    // Rejected, neither in the historic nor in the log
+    assert_eq!(
+        game.apply_event(Event::Choose(vec![])),
+        Err(Rejected::WrongState)
+    );
+    assert!(game.historic.is_empty());
+    game.apply_event(Event::PlayAll).unwrap();
+    let played: Vec<_> = game.log.iter().map(|entry| entry.logged).collect();
+    assert_eq!(
+        played,
+        names.into_iter().map(Logged::Played).collect::<Vec<_>>()
+    );
+
+    let gold = game[player].gold;
+    let card = *game.shop.iter().find(|card| card.gold <= gold).unwrap();
+    game.apply_event(Event::Buy(card.id)).unwrap();
+    let bought = *game.log.last().unwrap();
+    assert_eq!(
+        bought,
+        LogEntry {
+            turn: 1,
+            player,
+            logged: Logged::Bought {
+                card: card.name(),
+                gold: card.gold
+            }
+        }
+    );
+    assert!(bought.to_string().starts_with(&format!(
+        "Turn 1 — {player} bought {} for {}",
+        card.name(),
+        card.gold
+    )));
+
+    // Undoing an event forgets its entry
+    let len = game.log.len();
+    game.undo().unwrap();
+    assert_eq!(game.log.len(), len - 1);
+    game.apply_event(Event::NextTurn).unwrap();
+    assert_eq!(
+        game.log.last().unwrap().to_string(),
+        format!("Turn 1 — {player} ended the turn")
+    );
+
+    // The entries of the previous turns are kept
+    let len = game.log.len();
+    game.apply_event(Event::PlayAll).unwrap();
+    assert_eq!(game.undo(), Ok(()));
+    assert_eq!(game.log.len(), len);
+}
+
+#[cfg(feature = "tui")]
+#[test]
+pub fn log_panel_scrolls() {
+    use ratatui::{buffer::Buffer, layout::Rect, widgets::Widget};
+    use stars_gapa::{input::Input, tui::view::View};
+    let mut game = Game::with_seed(3);
+    let mut view = View::default();
+    let area = Rect::new(0, 0, 200, 50);
+    let text = |game: &Game, view: &View| {
+        let mut buf = Buffer::empty(area);
+        game.widget(view).render(area, &mut buf);
+        buf.content()
+            .iter()
+            .map(|cell| cell.symbol())
+            .collect::<String>()
+    };
+    game.apply_event(Event::PlayAll).unwrap();
+    game.apply_event(Event::NextTurn).unwrap();
+    assert!(!text(&game, &view).contains("ended the turn"));
+
+    view.interact(&mut game, Input::Log);
+    assert!(text(&game, &view).contains("ended the turn"));
+    view.interact(&mut game, Input::ScrollUp);
+    assert_eq!(view.log_scroll, 1);
+    let scrolled = text(&game, &view);
+    assert!(!scrolled.contains("ended the turn"));
+    assert!(scrolled.contains("1 more"));
+    view.interact(&mut game, Input::ScrollDown);
+    assert_eq!(view.log_scroll, 0);
+
+    view.interact(&mut game, Input::Log);
+    assert!(!text(&game, &view).contains("ended the turn"));
+}
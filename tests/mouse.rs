@@ -0,0 +1,78 @@
+#![cfg(feature = "tui")]
+
+use ratatui::{buffer::Buffer, layout::Rect, widgets::Widget};
+use stars_gapa::{
+    event::Event,
+    game::Game,
+    selection::Location,
+    tui::{
+        game::GameLayout,
+        view::{Mouse, View},
+    },
+};
+
+fn render(game: &Game, view: &View) -> Rect {
+    let area = Rect::new(0, 0, 160, 50);
+    let mut buf = Buffer::empty(area);
+    game.widget(view).render(area, &mut buf);
+    area
+}
+
+#[test]
+pub fn clicks_select_the_card_under_the_mouse() {
+    let game = Game::with_seed(7);
+    let mut view = View::default();
+    let layout = GameLayout::new(render(&game, &view));
+
+    // Clicking the explorer buys it
+    let explorer = layout.explorer;
+    let click = Mouse::Click {
+        column: explorer.x + explorer.width / 2,
+        row: explorer.y + explorer.height / 2,
+    };
+    assert_eq!(view.get_mouse_event(&game, click), Some(Event::BuyExplorer));
+    assert_eq!(view.position.loc, Location::Explorer);
+
+    // Hovering the last card of the hand does not move the cursor
+    let hand = layout.hand[0];
+    let last = game.players[game.current_player].hand.len() - 1;
+    let hover = Mouse::Hover {
+        column: hand.x + hand.width - 3,
+        row: hand.y + hand.height / 2,
+    };
+    assert_eq!(view.get_mouse_event(&game, hover), None);
+    assert_eq!(view.hover.as_ref().map(|pos| pos.index), Some(last));
+    assert_eq!(view.position.loc, Location::Explorer);
+
+    // Clicking it plays it
+    let click = Mouse::Click {
+        column: hand.x + hand.width - 3,
+        row: hand.y + hand.height / 2,
+    };
+    let id = game.players[game.current_player].hand[last].id;
+    assert_eq!(view.get_mouse_event(&game, click), Some(Event::Play(id)));
+
+    // Clicking the draw pile opens its popup over the game
+    let draw_pile = layout.draw_pile[0];
+    let click = Mouse::Click {
+        column: draw_pile.x + 1,
+        row: draw_pile.y + 1,
+    };
+    assert_eq!(view.get_mouse_event(&game, click), None);
+    assert_eq!(view.position.loc, Location::DrawPile);
+    render(&game, &view);
+    let click = Mouse::Click { column: 3, row: 3 };
+    view.get_mouse_event(&game, click);
+    assert_eq!(view.position.loc, Location::DrawPile);
+    assert_eq!(view.position.index, 0);
+}
+
+#[test]
+pub fn the_mouse_is_not_an_input() {
+    use stars_gapa::{input::Input, tui::view::Gesture};
+    assert!("click 12 4".parse::<Input>().is_err());
+    let click = Gesture::Mouse(Mouse::Click { column: 12, row: 4 });
+    assert_eq!("click 12 4".parse(), Ok(click));
+    assert_eq!(click.to_string(), "click 12 4");
+    assert_eq!("undo".parse(), Ok(Gesture::Input(Input::Undo)));
+}
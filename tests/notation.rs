@@ -0,0 +1,82 @@
+mod common;
+
+use common::random_game;
+use stars_gapa::{event::Event, game::Game, notation::Notation, player_id::PlayerId};
+
+#[test]
+pub fn games_are_replayed_from_their_notation() {
+    for seed in 0..20 {
+        let game = random_game(seed, 300, 10);
+        let text = game.notation().to_string();
+        let notation: Notation = text
+            .parse()
+            .unwrap_or_else(|error| panic!("{error}\n{text}"));
+        assert_eq!(notation.replay().state_hash(), game.state_hash(), "{text}");
+        assert_eq!(notation.to_string(), text);
+    }
+}
+
+#[test]
+pub fn moves_name_the_cards() {
+    let mut game = Game::with_seed(3);
+    let player = game.current_player;
+    game.apply_event(Event::PlayAll).unwrap();
+    game.apply_event(Event::NextTurn).unwrap();
+    let mut notation = game.notation();
+    notation.players[player] = String::from("Alice");
+    let text = notation.to_string();
+    assert!(text.starts_with("[Seed \"3\"]\n[Set \"core\"]\n"));
+    let tag = match player {
+        PlayerId::First => "First",
+        PlayerId::Second => "Second",
+    };
+    assert!(text.contains(&format!("[{tag} \"Alice\"]")));
+    assert!(text.contains("[Result \"*\"]"));
+    assert!(text.ends_with("\n\n1. Play all, End\n"));
+    assert_eq!(text.parse(), Ok(notation));
+
+    // The copies of a card are numbered
+    let game = Game::with_seed(3);
+    let hand = &game[game.current_player].hand;
+    let name = hand[0].name();
+    let copies = hand.iter().filter(|card| card.name() == name).count();
+    let moves: Vec<_> = (1..=copies)
+        .map(|copy| match copy {
+            1 => format!("Play {name}"),
+            _ => format!("Play {name} ({copy})"),
+        })
+        .collect();
+    let text = format!("[Seed \"3\"]\n1. {}\n", moves.join(", "));
+    let notation: Notation = text.parse().unwrap();
+    assert_eq!(notation.events.len(), copies);
+}
+
+#[test]
+pub fn wrong_notations_are_rejected() {
+    let parse = |text: &str| text.parse::<Notation>().unwrap_err();
+    assert_eq!(parse("1. End"), "line 1: missing the seed of the game");
+    assert_eq!(
+        parse("[Seed \"1\"]\n[Set \"promo\"]"),
+        "line 2: unknown set of cards 'promo'"
+    );
+    assert_eq!(
+        parse("[Seed \"1\"]\n2. End"),
+        "line 2: turn 2 is turn 1 of the game"
+    );
+    assert_eq!(
+        parse("[Seed \"1\"]\n1. Play Mothership"),
+        "line 2: no Mothership in the hand"
+    );
+    assert_eq!(
+        parse("[Seed \"1\"]\n1. Dance"),
+        "line 2: unknown move 'Dance'"
+    );
+    assert_eq!(
+        parse("[Seed \"1\"]\n1. Scrap nothing"),
+        "line 2: 'Scrap nothing' while the player does not scrap"
+    );
+    assert_eq!(
+        parse("[Seed \"1\"]\n[Result \"1st\"]\n1. End"),
+        "the result is '1st' but the moves give '*'"
+    );
+}
@@ -0,0 +1,109 @@
+mod common;
+
+use common::random_game;
+use stars_gapa::{
+    ai,
+    card::Card,
+    game::{Game, Viewer},
+    player_id::PlayerId,
+};
+
+#[test]
+pub fn positions_are_restored_from_their_code() {
+    for seed in 0..20 {
+        let mut game = random_game(seed, 40 + 10 * seed as usize, 0);
+        let code = game.position_code().to_string();
+        assert!(!code.contains('\n'));
+        let mut restored =
+            Game::from_position_code(&code).unwrap_or_else(|error| panic!("{error}\n{code}"));
+        assert_eq!(restored.state_hash(), game.state_hash(), "{code}");
+        assert_eq!(restored.position_code().to_string(), code);
+        // The generator is restored: both games draw the same cards
+        for _ in 0..30 {
+            let event = ai::choose_event(&game);
+            assert_eq!(restored.apply_event(event.clone()), game.apply_event(event));
+            assert_eq!(restored.state_hash(), game.state_hash(), "{code}");
+        }
+    }
+}
+
+#[test]
+pub fn seen_positions_hide_the_cards_and_the_seed() {
+    for seed in 0..10 {
+        let game = random_game(seed, 30 + 10 * seed as usize, 0);
+        for viewer in [
+            Viewer::Seat(PlayerId::First),
+            Viewer::Seat(PlayerId::Second),
+            Viewer::Spectator,
+        ] {
+            let seen = game.seen_by(viewer);
+            let code = seen.position_code().to_string();
+            let restored =
+                Game::from_position_code(&code).unwrap_or_else(|error| panic!("{error}\n{code}"));
+            assert_eq!(restored.state_hash(), seen.state_hash(), "{code}");
+            assert_eq!(restored.seed, 0);
+            assert!(restored.deck.iter().all(Card::is_hidden));
+            for player in [PlayerId::First, PlayerId::Second] {
+                let hand = &restored.players[player].hand;
+                assert_eq!(hand.len(), game.players[player].hand.len());
+                assert_eq!(
+                    hand.iter().all(Card::is_hidden),
+                    viewer != Viewer::Seat(player) || hand.is_empty()
+                );
+                assert!(!restored.players[player].discard.iter().any(Card::is_hidden));
+            }
+        }
+    }
+}
+
+#[test]
+pub fn invalid_codes_are_rejected() {
+    let code = Game::with_seed(5).position_code().to_string();
+    assert_eq!(
+        Game::from_position_code("5 1").unwrap_err(),
+        "expected 12 fields in a position code, found 2"
+    );
+    let fields: Vec<&str> = code.split(' ').collect();
+    let with = |index: usize, field: &str| {
+        let mut fields = fields.clone();
+        fields[index] = field;
+        Game::from_position_code(&fields.join(" "))
+    };
+    assert_eq!(with(1, "x").unwrap_err(), "'x' is not the turn");
+    assert_eq!(
+        with(3, "sleeping").unwrap_err(),
+        "'sleeping' is not a state"
+    );
+    assert_eq!(
+        with(5, "12ab").unwrap_err(),
+        "'12ab' is not the number of draws"
+    );
+    assert_eq!(with(4, "-1").unwrap_err(), "'-1' is not the next id");
+    // The draws are replayed, a huge number is refused at once
+    let started = std::time::Instant::now();
+    assert_eq!(
+        with(5, "18446744073709551615").unwrap_err(),
+        "18446744073709551615 draws of the generator, a code has at most 1048576"
+    );
+    assert!(started.elapsed() < std::time::Duration::from_secs(1));
+    assert_eq!(
+        with(1, "4294967296").unwrap_err(),
+        "'4294967296' is not the turn"
+    );
+    // Each card once, below the next id
+    let shop = fields[8];
+    let first = &shop[..shop[1..].find(char::is_alphabetic).unwrap() + 1];
+    assert_eq!(
+        with(8, &format!("{shop}{first}")).unwrap_err(),
+        format!("the card #{} is twice in the position", &first[1..])
+    );
+    assert!(
+        with(4, "3")
+            .unwrap_err()
+            .ends_with("is not below the next id 3")
+    );
+    assert!(with(6, "50:0:0/-/-/-/-").is_err());
+    assert_eq!(with(8, "%3").unwrap_err(), "'%' is not a card in '%3'");
+    assert_eq!(with(8, "b").unwrap_err(), "missing the id of a card in 'b'");
+    assert!(with(3, "scraping:1:shop").is_ok());
+}
@@ -0,0 +1,75 @@
+use stars_gapa::{
+    player_id::PlayerId,
+    rl::{Action, Env, NB_ACTIONS, OBSERVATION_SIZE, observation},
+};
+
+#[test]
+pub fn actions_round_trip_through_their_index() {
+    for index in 0..NB_ACTIONS {
+        assert_eq!(Action::from_index(index).unwrap().index(), index);
+    }
+    assert_eq!(Action::from_index(NB_ACTIONS), None);
+}
+
+#[test]
+pub fn legal_actions_play_their_events() {
+    let mut env = Env::new(100);
+    let observation = env.reset(7);
+    assert_eq!(observation.len(), OBSERVATION_SIZE);
+    let mask = env.action_mask();
+    assert!(mask[Action::NextTurn.index()]);
+    for (index, legal) in mask.iter().enumerate() {
+        let event = Action::from_index(index).unwrap().event(&env.game);
+        if *legal {
+            let event = event.unwrap();
+            assert_eq!(
+                Action::from_event(&env.game, &event),
+                Action::from_index(index)
+            );
+            assert!(env.game.clone().apply_event(event).is_ok());
+        }
+    }
+    let illegal = mask.iter().position(|legal| !legal).unwrap();
+    let before = env.game.state_hash();
+    assert_eq!(env.step(illegal), None);
+    assert_eq!(env.game.state_hash(), before);
+}
+
+#[test]
+pub fn observations_are_seen_by_a_player() {
+    let mut env = Env::new(100);
+    env.reset(3);
+    let current = env.game.current_player;
+    let mine = observation(&env.game, current);
+    let theirs = observation(&env.game, current.other());
+    assert_ne!(mine, theirs);
+    // The scalars end with the turn and whether the player is the current one
+    let scalars = OBSERVATION_SIZE - 7;
+    assert_eq!(mine[scalars - 2], env.game.turn as f32);
+    assert_eq!(mine[scalars - 1], 1.);
+    assert_eq!(theirs[scalars - 1], 0.);
+}
+
+#[test]
+pub fn episodes_end_with_rewards() {
+    let mut env = Env::new(200);
+    env.reset(11);
+    let step = loop {
+        // Prefer the last legal action: choosing, playing and buying before ending the turn
+        let mask = env.action_mask();
+        let action = mask.iter().rposition(|legal| *legal).unwrap();
+        let step = env.step(action).unwrap();
+        if step.done || step.truncated {
+            break step;
+        }
+        assert_eq!(step.rewards, [0., 0.]);
+    };
+    if step.done {
+        let winner = if step.rewards[PlayerId::First] == 1. {
+            PlayerId::First
+        } else {
+            PlayerId::Second
+        };
+        assert_eq!(step.rewards[winner.other()], -1.);
+    }
+}
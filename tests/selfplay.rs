@@ -0,0 +1,55 @@
+use rand::{Rng, SeedableRng};
+use stars_gapa::{
+    game::{Game, GameRng},
+    player_id::PlayerId,
+    rl::{Action, OBSERVATION_SIZE},
+    selfplay::{Agent, agent_seed, generate, play},
+};
+
+#[test]
+pub fn agents_do_not_draw_like_the_game() {
+    for seed in 0..20 {
+        let mut game = GameRng::seed_from_u64(seed);
+        let mut agent = GameRng::seed_from_u64(agent_seed(seed));
+        let game: Vec<u64> = (0..8).map(|_| game.next_u64()).collect();
+        let agent: Vec<u64> = (0..8).map(|_| agent.next_u64()).collect();
+        assert!(game.iter().all(|word| !agent.contains(word)), "{seed}");
+    }
+}
+
+#[test]
+pub fn decisions_know_the_outcome() {
+    let decisions = play(5, [Agent::Greedy, Agent::Random], 1000);
+    let winner = decisions.last().unwrap().player;
+    for decision in &decisions {
+        assert_eq!(decision.seed, 5);
+        let expected = if decision.player == winner { 1 } else { -1 };
+        assert_eq!(decision.outcome, expected);
+    }
+    assert!(decisions.iter().any(|d| d.player == PlayerId::Second));
+
+    // The decisions are the events applied, with their actions
+    let mut game = Game::with_seed(5);
+    for decision in &decisions {
+        let action = Action::from_event(&game, &decision.event).map(|action| action.index());
+        assert_eq!(action, decision.action, "{}", decision.event);
+        game.apply_event(decision.event.clone()).unwrap();
+    }
+}
+
+#[test]
+pub fn threads_do_not_change_the_output() {
+    let agents = [Agent::Random, Agent::Greedy];
+    let mut one = Vec::new();
+    let mut four = Vec::new();
+    let nb = generate(10, 6, 1, agents, 30, &mut one).unwrap();
+    assert_eq!(generate(10, 6, 4, agents, 30, &mut four).unwrap(), nb);
+    assert_eq!(one, four);
+    let text = String::from_utf8(one).unwrap();
+    assert_eq!(text.lines().count(), nb);
+    let first = text.lines().next().unwrap();
+    assert!(first.starts_with(r#"{"seed":10,"turn":1,"player":"1st","observation":["#));
+    let observation = first.split_once('[').unwrap().1.split_once(']').unwrap().0;
+    assert_eq!(observation.split(',').count(), OBSERVATION_SIZE);
+    assert!(text.lines().any(|line| line.starts_with(r#"{"seed":15,"#)));
+}
@@ -0,0 +1,155 @@
+#![cfg(feature = "tui")]
+
+mod common;
+
+use ratatui::{Terminal, backend::TestBackend, buffer::Buffer, layout::Rect, widgets::Widget};
+use stars_gapa::{
+    app::catalog,
+    event::Event,
+    game::Game,
+    selection::{GamePosition, Location},
+    theme::{Palette, Theme},
+    tui::view::View,
+};
+use std::path::PathBuf;
+
+/// Compares a rendered buffer with the snapshot `tests/snapshots/{name}.txt`
+///
+/// `UPDATE_SNAPSHOTS=1` writes the snapshots, a missing snapshot fails otherwise.
+fn assert_snapshot(name: &str, buf: &Buffer) {
+    let text = lines(buf);
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/snapshots")
+        .join(format!("{name}.txt"));
+    if std::env::var_os("UPDATE_SNAPSHOTS").is_some_and(|update| update == "1") {
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(&path, &text).unwrap();
+        return;
+    }
+    let Ok(expected) = std::fs::read_to_string(&path) else {
+        panic!(
+            "{name} has no snapshot {}, run with UPDATE_SNAPSHOTS=1 to write it\n{text}",
+            path.display()
+        );
+    };
+    if expected != text {
+        panic!(
+            "{name} differs from its snapshot {}, run with UPDATE_SNAPSHOTS=1 to accept it\n{text}",
+            path.display()
+        );
+    }
+}
+
+/// The symbols of the buffer, one line per row, then the styles
+/// with the cell where they start: `x,y fg bg modifiers`
+fn lines(buf: &Buffer) -> String {
+    let mut text = String::new();
+    for y in buf.area.top()..buf.area.bottom() {
+        for x in buf.area.left()..buf.area.right() {
+            text.push_str(buf[(x, y)].symbol());
+        }
+        text.push('\n');
+    }
+    text.push_str("\nstyles:\n");
+    let mut last = None;
+    for y in buf.area.top()..buf.area.bottom() {
+        for x in buf.area.left()..buf.area.right() {
+            let cell = &buf[(x, y)];
+            let style = (cell.fg, cell.bg, cell.modifier);
+            if last != Some(style) {
+                text.push_str(&format!(
+                    "{x},{y} {:?} {:?} {:?}\n",
+                    style.0, style.1, style.2
+                ));
+                last = Some(style);
+            }
+        }
+    }
+    text
+}
+
+/// Renders a widget on a [TestBackend] of a given size
+fn draw(width: u16, height: u16, widget: impl Widget) -> Buffer {
+    let mut terminal = Terminal::new(TestBackend::new(width, height)).unwrap();
+    terminal
+        .draw(|frame| frame.render_widget(widget, frame.area()))
+        .unwrap();
+    terminal.backend().buffer().clone()
+}
+
+/// A game in the middle of the first turn:
+/// a card is played, another one is hovered and a card of the shop is selected
+fn position() -> (Game, View) {
+    let mut game = Game::with_seed(3);
+    let player = game.current_player;
+    let id = game.players[player].hand[0].id;
+    game.apply_event(Event::Play(id)).unwrap();
+    let blob = catalog()
+        .into_iter()
+        .find(|card| card.name() == "Blob Fighter")
+        .unwrap();
+    game.players[player].played.push(blob);
+    game.players[player].gold = 3;
+    let view = View {
+        position: GamePosition {
+            player,
+            loc: Location::Shop,
+            index: 1,
+        },
+        hover: Some(GamePosition {
+            player,
+            loc: Location::Hand,
+            index: 0,
+        }),
+        ..View::default()
+    };
+    (game, view)
+}
+
+#[test]
+pub fn game_at_several_sizes() {
+    let _theme = common::theme_lock(Theme::Ascii, Palette::Normal);
+    let (game, view) = position();
+    for (name, width, height) in [
+        ("game_too_small", 50, 15),
+        ("game_compact", 80, 24),
+        ("game_normal", 160, 50),
+        ("game_wide", 220, 60),
+    ] {
+        assert_snapshot(name, &draw(width, height, game.widget(&view)));
+    }
+}
+
+#[test]
+pub fn deck_popups() {
+    let _theme = common::theme_lock(Theme::Ascii, Palette::Normal);
+    let (game, mut view) = position();
+    view.position = GamePosition {
+        player: game.current_player,
+        loc: Location::DrawPile,
+        index: 2,
+    };
+    assert_snapshot("popup_normal", &draw(160, 50, game.widget(&view)));
+    assert_snapshot("popup_compact", &draw(80, 24, game.widget(&view)));
+}
+
+#[test]
+pub fn card_styles() {
+    let _theme = common::theme_lock(Theme::Ascii, Palette::Normal);
+    let card = catalog()
+        .into_iter()
+        .find(|card| card.name() == "Blob Fighter")
+        .unwrap();
+    let area = Rect::new(0, 0, 18, 6);
+    let styles = [
+        ("card", card.widget()),
+        ("card_selected", card.widget().selected()),
+        ("card_hovered", card.widget().set_hover(true)),
+        ("card_dimmed", card.widget().set_dimmed(true)),
+    ];
+    for (name, widget) in styles {
+        let mut buf = Buffer::empty(area);
+        widget.render(area, &mut buf);
+        assert_snapshot(name, &buf);
+    }
+}
@@ -0,0 +1,30 @@
+#![cfg(feature = "tui")]
+
+mod common;
+
+use stars_gapa::{
+    action::Action,
+    app::catalog,
+    faction::Faction,
+    player::Player,
+    theme::{ASCII, Palette, Theme},
+};
+
+#[test]
+pub fn ascii_theme() {
+    let _theme = common::theme_lock(Theme::Emoji, Palette::Normal);
+    assert_eq!(Theme::current(), Theme::Emoji);
+    assert_eq!(format!("{}", Action::Gold(3)), "3🪙");
+
+    "ascii".parse::<Theme>().unwrap().set();
+    assert_eq!(format!("{}", Action::Gold(3)), "3$");
+    assert_eq!(format!("{}", Faction::Blob), ASCII.factions[0]);
+    assert_eq!(format!("{}", Player::default()), "0$\n0*\n50+");
+    for card in catalog() {
+        let text = format!("{}{}", card, card.faction);
+        assert!(text.is_ascii(), "{} is displayed with {text}", card.name());
+    }
+
+    Theme::current().next().set();
+    assert_eq!(Theme::current(), Theme::Emoji);
+}
@@ -0,0 +1,58 @@
+#![cfg(feature = "tui")]
+
+use ratatui::{buffer::Buffer, layout::Rect, widgets::Widget};
+use stars_gapa::{
+    card::Card,
+    game::Game,
+    input::Input,
+    player_id::PlayerId,
+    selection::{GamePosition, Location},
+    tui::view::View,
+};
+
+#[test]
+pub fn action_sentences() {
+    let frigate = Card::imperial_frigate();
+    let sentences: Vec<String> = frigate
+        .iter()
+        .map(|action| action.sentence().to_string())
+        .collect();
+    assert_eq!(
+        sentences,
+        [
+            "Gain 4 attack.",
+            "Your opponent discards 1 card.",
+            "If another Star Empire card is played, gain 2 attack.",
+            "Scrap this card to draw 1 card.",
+        ]
+    );
+}
+
+#[test]
+pub fn zoom_on_the_card_under_the_cursor() {
+    let mut game = Game::with_seed(1);
+    let mut view = View::default();
+    let area = Rect::new(0, 0, 160, 50);
+    let text = |game: &Game, view: &View| {
+        let mut buf = Buffer::empty(area);
+        game.widget(view).render(area, &mut buf);
+        buf.content()
+            .iter()
+            .map(|cell| cell.symbol())
+            .collect::<String>()
+    };
+
+    view.position = GamePosition {
+        player: PlayerId::First,
+        loc: Location::Explorer,
+        index: 0,
+    };
+    assert!(!text(&game, &view).contains("Cost: 2 gold"));
+    view.interact(&mut game, Input::Zoom);
+    let zoomed = text(&game, &view);
+    assert!(zoomed.contains("Explorer"));
+    assert!(zoomed.contains("Cost: 2 gold"));
+    assert!(zoomed.contains("Scrap this card to gain 2 attack."));
+    view.interact(&mut game, Input::Zoom);
+    assert!(!text(&game, &view).contains("Cost: 2 gold"));
+}